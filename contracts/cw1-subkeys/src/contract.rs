@@ -17,7 +17,9 @@ use cw1_whitelist::{
     msg::InstantiateMsg,
     state::ADMIN_LIST,
 };
-use cw2::{get_contract_version, set_contract_version};
+use cw2::{
+    get_contract_version, set_contract_version, set_supported_interfaces, ContractInterface,
+};
 use cw_storage_plus::Bound;
 use cw_utils::Expiration;
 use semver::Version;
@@ -33,6 +35,14 @@ use crate::state::{Allowance, Permissions, ALLOWANCES, PERMISSIONS};
 const CONTRACT_NAME: &str = "crates.io:cw1-subkeys";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw1::INTERFACE,
+        cw1::INTERFACE_VERSION,
+    )]
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -42,6 +52,7 @@ pub fn instantiate(
 ) -> StdResult<Response> {
     let result = whitelist_instantiate(deps.branch(), env, info, msg)?;
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     Ok(result)
 }
 
@@ -464,6 +475,7 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, Contra
         // If state structure changed in any contract version in the way migration is needed, it
         // should occur here
     }
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
 
     Ok(Response::new())
 }
@@ -476,7 +488,7 @@ mod tests {
     use cosmwasm_std::{coin, coins, OwnedDeps, StakingMsg, SubMsg, Timestamp};
 
     use cw1_whitelist::msg::AdminListResponse;
-    use cw2::{get_contract_version, ContractVersion};
+    use cw2::{get_contract_version, get_supported_interfaces, ContractVersion};
     use cw_utils::NativeBalance;
 
     use crate::state::Permissions;
@@ -679,7 +691,11 @@ mod tests {
                 version: CONTRACT_VERSION.to_string(),
             },
             get_contract_version(&deps.storage).unwrap()
-        )
+        );
+        assert_eq!(
+            vec![ContractInterface::new("cw1", cw1::INTERFACE_VERSION)],
+            get_supported_interfaces(&deps.storage).unwrap()
+        );
     }
 
     mod allowance {
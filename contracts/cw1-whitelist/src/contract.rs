@@ -9,7 +9,7 @@ use cosmwasm_std::{
 };
 
 use cw1::CanExecuteResponse;
-use cw2::set_contract_version;
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
 
 use crate::error::ContractError;
 use crate::msg::{AdminListResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -19,6 +19,14 @@ use crate::state::{AdminList, ADMIN_LIST};
 const CONTRACT_NAME: &str = "crates.io:cw1-whitelist";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw1::INTERFACE,
+        cw1::INTERFACE_VERSION,
+    )]
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -27,6 +35,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     let cfg = AdminList {
         admins: map_validate(deps.api, &msg.admins)?,
         mutable: msg.mutable,
@@ -111,7 +120,7 @@ pub fn execute_update_admins(
 
 fn can_execute(deps: Deps, sender: &str) -> StdResult<bool> {
     let cfg = ADMIN_LIST.load(deps.storage)?;
-    let can = cfg.is_admin(&sender);
+    let can = cfg.is_admin(sender);
     Ok(can)
 }
 
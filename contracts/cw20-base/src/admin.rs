@@ -0,0 +1,215 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+use cw20::TokenAdminResponse;
+
+use crate::error::ContractError;
+use crate::msg::{validate_name, validate_symbol};
+use crate::state::{TOKEN_ADMIN, TOKEN_INFO};
+
+fn ensure_token_admin(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let admin = TOKEN_ADMIN
+        .may_load(storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if admin != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_token_info(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: Option<String>,
+    symbol: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_token_admin(deps.storage, &info.sender)?;
+
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    if let Some(name) = name {
+        validate_name(&name)?;
+        config.name = name;
+    }
+    if let Some(symbol) = symbol {
+        validate_symbol(&symbol)?;
+        config.symbol = symbol;
+    }
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_token_info")
+        .add_attribute("name", config.name)
+        .add_attribute("symbol", config.symbol))
+}
+
+pub fn execute_update_cap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cap: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    ensure_token_admin(deps.storage, &info.sender)?;
+
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    let total_supply = config.total_supply;
+    let mint = config.mint.as_mut().ok_or(ContractError::NotMintable {})?;
+    if let Some(cap) = cap {
+        if cap < total_supply {
+            return Err(ContractError::CapBelowSupply {});
+        }
+    }
+    mint.cap = cap;
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_cap")
+        .add_attribute(
+            "cap",
+            cap.map(|c| c.to_string())
+                .unwrap_or_else(|| "None".to_string()),
+        ))
+}
+
+pub fn query_token_admin(deps: Deps) -> StdResult<TokenAdminResponse> {
+    let admin = TOKEN_ADMIN.may_load(deps.storage)?.map(Into::into);
+    Ok(TokenAdminResponse { admin })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::StdError;
+    use cw20::{MinterResponse, TokenInfoResponse};
+
+    use crate::contract::{execute, query_minter, query_token_info};
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::test_helpers::{instantiate_token, token_msg, OWNER};
+
+    const ADMIN: &str = "admin";
+    const MINTER: &str = "minter";
+
+    fn do_instantiate(deps: DepsMut, mint: Option<MinterResponse>) {
+        let msg = InstantiateMsg {
+            mint,
+            admin: Some(ADMIN.into()),
+            ..token_msg()
+        };
+        instantiate_token(deps, mock_env(), msg);
+    }
+
+    fn minter(cap: Option<u128>) -> Option<MinterResponse> {
+        Some(MinterResponse {
+            minter: MINTER.into(),
+            cap: cap.map(Uint128::new),
+        })
+    }
+
+    #[test]
+    fn only_admin_updates_token_info() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+        assert_eq!(
+            query_token_admin(deps.as_ref()).unwrap().admin,
+            Some(ADMIN.to_string())
+        );
+
+        let msg = ExecuteMsg::UpdateTokenInfo {
+            name: Some("Rebranded".into()),
+            symbol: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // fields left empty keep their value
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap(),
+            TokenInfoResponse {
+                name: "Rebranded".into(),
+                symbol: "AUTO".into(),
+                decimals: 3,
+                total_supply: Uint128::new(1000),
+            }
+        );
+
+        // same rules as on instantiation
+        let msg = ExecuteMsg::UpdateTokenInfo {
+            name: None,
+            symbol: Some("A1".into()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::generic_err(
+                "Ticker symbol is not in expected format [a-zA-Z\\-]{3,12}"
+            ))
+        );
+        let msg = ExecuteMsg::UpdateTokenInfo {
+            name: Some("ab".into()),
+            symbol: Some("NEW".into()),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(query_token_info(deps.as_ref()).unwrap().symbol, "AUTO");
+    }
+
+    #[test]
+    fn update_cap() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), minter(Some(2000)));
+
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(1500)),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(query_minter(deps.as_ref()).unwrap(), minter(Some(1500)));
+
+        // the cap may equal the supply, but not go below it
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(999)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CapBelowSupply {});
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(1000)),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Mint {
+            recipient: "rcpt".into(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+
+        // removing the cap
+        let msg = ExecuteMsg::UpdateCap { cap: None };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(query_minter(deps.as_ref()).unwrap(), minter(None));
+    }
+
+    #[test]
+    fn update_cap_requires_minter() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+
+        let msg = ExecuteMsg::UpdateCap {
+            cap: Some(Uint128::new(5000)),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NotMintable {});
+    }
+}
@@ -1,11 +1,26 @@
 use cosmwasm_std::{
-    attr, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Storage, Uint128,
+    attr, to_vec, Addr, Binary, BlockInfo, CanonicalAddr, Deps, DepsMut, Env, Event, MessageInfo,
+    Order, Response, StdError, StdResult, Storage, Uint128,
 };
-use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
+use cw20::events::{AllowanceChangedEvent, BurnEvent, SendEvent, TransferEvent};
+use cw20::{
+    AllowanceResponse, Cw20ApprovalReceiveMsg, Cw20Coin, Cw20ReceiveMsg, Expiration,
+    PermitNonceResponse, PermitPayload, RecurringAllowance,
+};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
+use crate::compliance::ensure_compliant;
+use crate::contract::{ensure_not_paused, validate_batch};
 use crate::error::ContractError;
-use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_INFO};
+use crate::fee::{charge_transfer_fee, fee_attributes};
+use crate::memo::{ensure_valid_memo, memo_attributes};
+use crate::state::{balances, ALLOWANCES, ALLOWANCES_SPENDER, PERMIT_NONCES, TOKEN_INFO};
+use crate::vesting::ensure_spendable;
+
+// how many expired allowances a single prune removes at most
+const MAX_PRUNE_LIMIT: u32 = 100;
+const DEFAULT_PRUNE_LIMIT: u32 = 30;
 
 pub fn execute_increase_allowance(
     deps: DepsMut,
@@ -16,14 +31,81 @@ pub fn execute_increase_allowance(
     expires: Option<Expiration>,
 ) -> Result<Response, ContractError> {
     let spender_addr = deps.api.addr_validate(&spender)?;
-    if spender_addr == info.sender {
+    let allowance = increase_allowance(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        &spender_addr,
+        amount,
+        expires,
+    )?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "increase_allowance"),
+            attr("owner", &info.sender),
+            attr("spender", spender),
+            attr("amount", amount),
+        ])
+        .add_event(allowance_changed(&info.sender, &spender_addr, &allowance));
+    Ok(res)
+}
+
+pub fn execute_increase_allowance_and_call(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let allowance = increase_allowance(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        &spender_addr,
+        amount,
+        expires,
+    )?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "increase_allowance_and_call"),
+            attr("owner", &info.sender),
+            attr("spender", &spender),
+            attr("amount", amount),
+        ])
+        .add_event(allowance_changed(&info.sender, &spender_addr, &allowance))
+        .add_message(
+            Cw20ApprovalReceiveMsg {
+                owner: info.sender.into(),
+                amount,
+                msg,
+            }
+            .into_cosmos_msg(spender)?,
+        );
+    Ok(res)
+}
+
+fn increase_allowance(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<AllowanceResponse, ContractError> {
+    if spender == owner {
         return Err(ContractError::CannotSetOwnAccount {});
     }
 
     let update_fn = |allow: Option<AllowanceResponse>| -> Result<_, _> {
         let mut val = allow.unwrap_or_default();
+        refill_allowance(&mut val, block);
         if let Some(exp) = expires {
-            if exp.is_expired(&env.block) {
+            if exp.is_expired(block) {
                 return Err(ContractError::InvalidExpiration {});
             }
             val.expires = exp;
@@ -31,18 +113,157 @@ pub fn execute_increase_allowance(
         val.allowance += amount;
         Ok(val)
     };
-    ALLOWANCES.update(deps.storage, (&info.sender, &spender_addr), update_fn)?;
-    ALLOWANCES_SPENDER.update(deps.storage, (&spender_addr, &info.sender), update_fn)?;
+    let allowance = ALLOWANCES.update(storage, (owner, spender), update_fn)?;
+    ALLOWANCES_SPENDER.update(storage, (spender, owner), update_fn)?;
+    Ok(allowance)
+}
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "increase_allowance"),
-        attr("owner", info.sender),
-        attr("spender", spender),
-        attr("amount", amount),
-    ]);
+/// The typed event reporting the new state of an allowance.
+fn allowance_changed(owner: &Addr, spender: &Addr, allowance: &AllowanceResponse) -> Event {
+    AllowanceChangedEvent {
+        owner: owner.into(),
+        spender: spender.into(),
+        allowance: allowance.allowance,
+        expires: allowance.expires,
+    }
+    .into_event()
+}
+
+/// Grants an allowance on behalf of the owner of `owner_pubkey`, who signed the sha256 hash
+/// of the JSON encoded `PermitPayload`. The owner address is derived from the public key
+/// the same way the chain does, so the sender only relays the signature.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_permit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner_pubkey: Binary,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+    nonce: u64,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    if owner_pubkey.len() != 33 {
+        return Err(ContractError::InvalidPermitPubkey {});
+    }
+
+    let payload = PermitPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        spender: spender.clone(),
+        amount,
+        expires,
+        nonce,
+    };
+    let hash = Sha256::digest(to_vec(&payload)?);
+    let valid = deps
+        .api
+        .secp256k1_verify(&hash, &signature, &owner_pubkey)
+        .map_err(StdError::from)?;
+    if !valid {
+        return Err(ContractError::InvalidPermitSignature {});
+    }
+
+    let owner = deps.api.addr_humanize(&CanonicalAddr::from(
+        Ripemd160::digest(Sha256::digest(owner_pubkey.as_slice())).as_slice(),
+    ))?;
+    let expected = PERMIT_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidPermitNonce { expected });
+    }
+
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let allowance = increase_allowance(
+        deps.storage,
+        &env.block,
+        &owner,
+        &spender_addr,
+        amount,
+        expires,
+    )?;
+    PERMIT_NONCES.save(deps.storage, &owner, &(nonce + 1))?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "permit"),
+            attr("owner", &owner),
+            attr("spender", spender),
+            attr("amount", amount),
+            attr("nonce", nonce.to_string()),
+            attr("relayer", info.sender),
+        ])
+        .add_event(allowance_changed(&owner, &spender_addr, &allowance));
     Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+    period: Option<u64>,
+    recipients: Option<Vec<String>>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+    if period == Some(0) {
+        return Err(ContractError::InvalidAllowancePeriod {});
+    }
+    let recipients = recipients
+        .map(|recipients| {
+            recipients
+                .iter()
+                .map(|r| deps.api.addr_validate(r).map(Into::into))
+                .collect::<StdResult<_>>()
+        })
+        .transpose()?;
+
+    let allowance = AllowanceResponse {
+        allowance: amount,
+        expires,
+        recurring: period.map(|period| RecurringAllowance { amount, period }),
+        next_reset: period.map(|period| env.block.time.plus_seconds(period)),
+        recipients,
+    };
+    ALLOWANCES.save(deps.storage, (&info.sender, &spender_addr), &allowance)?;
+    ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &info.sender), &allowance)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "set_allowance"),
+            attr("owner", &info.sender),
+            attr("spender", spender),
+            attr("amount", amount),
+        ])
+        .add_event(allowance_changed(&info.sender, &spender_addr, &allowance));
+    Ok(res)
+}
+
+/// Refills a recurring allowance if one or more periods have ended since it was last used.
+/// Periods follow each other without gaps, so the reset time does not drift.
+pub fn refill_allowance(allowance: &mut AllowanceResponse, block: &BlockInfo) {
+    if let (Some(recurring), Some(next_reset)) = (&allowance.recurring, allowance.next_reset) {
+        if block.time >= next_reset {
+            let elapsed = block.time.seconds() - next_reset.seconds();
+            let periods = elapsed / recurring.period + 1;
+            allowance.allowance = recurring.amount;
+            allowance.next_reset = Some(next_reset.plus_seconds(periods * recurring.period));
+        }
+    }
+}
+
 pub fn execute_decrease_allowance(
     deps: DepsMut,
     env: Env,
@@ -62,14 +283,13 @@ pub fn execute_decrease_allowance(
         (t.1, t.0)
     }
 
-    // load value and delete if it hits 0, or update otherwise
+    // load value and delete if it hits 0, or update otherwise.
+    // A recurring allowance only loses the rest of the current period and is refilled later.
     let mut allowance = ALLOWANCES.load(deps.storage, key)?;
-    if amount < allowance.allowance {
+    refill_allowance(&mut allowance, &env.block);
+    if amount < allowance.allowance || allowance.recurring.is_some() {
         // update the new amount
-        allowance.allowance = allowance
-            .allowance
-            .checked_sub(amount)
-            .map_err(StdError::overflow)?;
+        allowance.allowance = allowance.allowance.saturating_sub(amount);
         if let Some(exp) = expires {
             if exp.is_expired(&env.block) {
                 return Err(ContractError::InvalidExpiration {});
@@ -81,44 +301,101 @@ pub fn execute_decrease_allowance(
     } else {
         ALLOWANCES.remove(deps.storage, key);
         ALLOWANCES_SPENDER.remove(deps.storage, reverse(key));
+        allowance.allowance = Uint128::zero();
     }
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "decrease_allowance"),
-        attr("owner", info.sender),
-        attr("spender", spender),
-        attr("amount", amount),
-    ]);
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "decrease_allowance"),
+            attr("owner", &info.sender),
+            attr("spender", spender),
+            attr("amount", amount),
+        ])
+        .add_event(allowance_changed(&info.sender, &spender_addr, &allowance));
     Ok(res)
 }
 
-// this can be used to update a lower allowance - call bucket.update with proper keys
+// this can be used to update a lower allowance. An allowance used up is removed, unless it
+// is refilled in the next period. `recipient` is None when the tokens are burnt.
 pub fn deduct_allowance(
     storage: &mut dyn Storage,
     owner: &Addr,
     spender: &Addr,
+    recipient: Option<&Addr>,
     block: &BlockInfo,
     amount: Uint128,
 ) -> Result<AllowanceResponse, ContractError> {
-    let update_fn = |current: Option<AllowanceResponse>| -> _ {
-        match current {
-            Some(mut a) => {
-                if a.expires.is_expired(block) {
-                    Err(ContractError::Expired {})
-                } else {
-                    // deduct the allowance if enough
-                    a.allowance = a
-                        .allowance
-                        .checked_sub(amount)
-                        .map_err(StdError::overflow)?;
-                    Ok(a)
-                }
-            }
-            None => Err(ContractError::NoAllowance {}),
+    let mut a = ALLOWANCES
+        .may_load(storage, (owner, spender))?
+        .ok_or(ContractError::NoAllowance {})?;
+    refill_allowance(&mut a, block);
+    if a.expires.is_expired(block) {
+        return Err(ContractError::Expired {});
+    }
+    if !covers_recipient(&a, recipient) {
+        return Err(ContractError::RecipientNotAllowed {});
+    }
+
+    // deduct the allowance if enough
+    a.allowance = a
+        .allowance
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    if a.allowance.is_zero() && a.recurring.is_none() {
+        ALLOWANCES.remove(storage, (owner, spender));
+        ALLOWANCES_SPENDER.remove(storage, (spender, owner));
+    } else {
+        ALLOWANCES.save(storage, (owner, spender), &a)?;
+        ALLOWANCES_SPENDER.save(storage, (spender, owner), &a)?;
+    }
+    Ok(a)
+}
+
+/// Removes up to `limit` expired allowances of `owner` from both allowance maps.
+pub fn execute_prune_expired_allowances(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    owner: String,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_PRUNE_LIMIT).min(MAX_PRUNE_LIMIT) as usize;
+
+    let mut expired = vec![];
+    for item in ALLOWANCES
+        .prefix(&owner_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        if expired.len() == limit {
+            break;
         }
-    };
-    ALLOWANCES.update(storage, (owner, spender), update_fn)?;
-    ALLOWANCES_SPENDER.update(storage, (spender, owner), update_fn)
+        let (spender, allowance) = item?;
+        if allowance.expires.is_expired(&env.block) {
+            expired.push((spender, allowance));
+        }
+    }
+
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "prune_expired_allowances"),
+        attr("owner", owner),
+        attr("pruned", expired.len().to_string()),
+    ]);
+    for (spender, mut allowance) in expired {
+        ALLOWANCES.remove(deps.storage, (&owner_addr, &spender));
+        ALLOWANCES_SPENDER.remove(deps.storage, (&spender, &owner_addr));
+        allowance.allowance = Uint128::zero();
+        res = res.add_event(allowance_changed(&owner_addr, &spender, &allowance));
+    }
+    Ok(res)
+}
+
+fn covers_recipient(allowance: &AllowanceResponse, recipient: Option<&Addr>) -> bool {
+    match (&allowance.recipients, recipient) {
+        (None, _) => true,
+        (Some(recipients), Some(recipient)) => recipients.iter().any(|r| r == recipient.as_str()),
+        (Some(_), None) => false,
+    }
 }
 
 pub fn execute_transfer_from(
@@ -128,72 +405,195 @@ pub fn execute_transfer_from(
     owner: String,
     recipient: String,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
+    ensure_valid_memo(deps.storage, memo.as_deref())?;
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
+    ensure_compliant(deps.storage, Some(&owner_addr), &rcpt_addr)?;
+    ensure_spendable(deps.storage, &env.block, &owner_addr, amount)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(
+        deps.storage,
+        &owner_addr,
+        &info.sender,
+        Some(&rcpt_addr),
+        &env.block,
+        amount,
+    )?;
 
-    BALANCES.update(
+    balances().update(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let fee = charge_transfer_fee(
+        deps.storage,
+        env.block.height,
+        &owner_addr,
+        &rcpt_addr,
+        amount,
+    )?;
+    let net_amount = amount - fee;
+    balances().update(
         deps.storage,
         &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + net_amount) },
     )?;
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "transfer_from"),
-        attr("from", owner),
-        attr("to", recipient),
-        attr("by", info.sender),
-        attr("amount", amount),
-    ]);
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "transfer_from"),
+            attr("from", owner),
+            attr("to", recipient),
+            attr("by", &info.sender),
+            attr("amount", amount),
+        ])
+        .add_attributes(fee_attributes(amount, fee))
+        .add_attributes(memo_attributes(memo.as_deref()))
+        .add_event(
+            TransferEvent {
+                from: owner_addr.to_string(),
+                to: rcpt_addr.to_string(),
+                amount,
+                by: Some(info.sender.to_string()),
+                memo,
+            }
+            .into_event()
+            .add_attributes(fee_attributes(amount, fee)),
+        )
+        .add_event(allowance_changed(&owner_addr, &info.sender, &allowance));
     Ok(res)
 }
 
 pub fn execute_burn_from(
     deps: DepsMut,
-
     env: Env,
     info: MessageInfo,
     owner: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
+    ensure_spendable(deps.storage, &env.block, &owner_addr, amount)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(
+        deps.storage,
+        &owner_addr,
+        &info.sender,
+        None,
+        &env.block,
+        amount,
+    )?;
 
     // lower balance
-    BALANCES.update(
+    balances().update(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
-        meta.total_supply = meta.total_supply.checked_sub(amount)?;
-        Ok(meta)
-    })?;
-
-    let res = Response::new().add_attributes(vec![
-        attr("action", "burn_from"),
-        attr("from", owner),
-        attr("by", info.sender),
-        attr("amount", amount),
+    let mut meta = TOKEN_INFO.load(deps.storage)?;
+    meta.total_supply = meta
+        .total_supply
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    TOKEN_INFO.save(deps.storage, &meta, env.block.height)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "burn_from"),
+            attr("from", owner),
+            attr("by", &info.sender),
+            attr("amount", amount),
+        ])
+        .add_event(
+            BurnEvent {
+                from: owner_addr.to_string(),
+                amount,
+                by: Some(info.sender.to_string()),
+                memo: None,
+            }
+            .into_event(),
+        )
+        .add_event(allowance_changed(&owner_addr, &info.sender, &allowance));
+    Ok(res)
+}
+
+/// Burns from the accounts of several owners, deducting each owner's allowance, and updates
+/// the total supply once.
+pub fn execute_burn_from_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    burns: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
+    let (owners, total) = validate_batch(
+        deps.api,
+        burns.iter().map(|b| (b.address.as_str(), b.amount)),
+    )?;
+    for (owner_addr, burn) in owners.iter().zip(&burns) {
+        ensure_spendable(deps.storage, &env.block, owner_addr, burn.amount)?;
+    }
+
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "burn_from_batch"),
+        attr("by", &info.sender),
+        attr("amount", total),
     ]);
+    for (owner_addr, burn) in owners.iter().zip(burns) {
+        let allowance = deduct_allowance(
+            deps.storage,
+            owner_addr,
+            &info.sender,
+            None,
+            &env.block,
+            burn.amount,
+        )?;
+        balances().update(
+            deps.storage,
+            owner_addr,
+            env.block.height,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_sub(burn.amount)?)
+            },
+        )?;
+        res = res
+            .add_event(
+                BurnEvent {
+                    from: owner_addr.to_string(),
+                    amount: burn.amount,
+                    by: Some(info.sender.to_string()),
+                    memo: None,
+                }
+                .into_event(),
+            )
+            .add_event(allowance_changed(owner_addr, &info.sender, &allowance));
+    }
+
+    // reduce total_supply once for the whole batch
+    let mut meta = TOKEN_INFO.load(deps.storage)?;
+    meta.total_supply = meta
+        .total_supply
+        .checked_sub(total)
+        .map_err(StdError::overflow)?;
+    TOKEN_INFO.save(deps.storage, &meta, env.block.height)?;
+
     Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_send_from(
     deps: DepsMut,
     env: Env,
@@ -202,53 +602,107 @@ pub fn execute_send_from(
     contract: String,
     amount: Uint128,
     msg: Binary,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
+    ensure_valid_memo(deps.storage, memo.as_deref())?;
     let rcpt_addr = deps.api.addr_validate(&contract)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
+    ensure_compliant(deps.storage, Some(&owner_addr), &rcpt_addr)?;
+    ensure_spendable(deps.storage, &env.block, &owner_addr, amount)?;
 
     // deduct allowance before doing anything else have enough allowance
-    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
+    let allowance = deduct_allowance(
+        deps.storage,
+        &owner_addr,
+        &info.sender,
+        Some(&rcpt_addr),
+        &env.block,
+        amount,
+    )?;
 
     // move the tokens to the contract
-    BALANCES.update(
+    balances().update(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let fee = charge_transfer_fee(
         deps.storage,
+        env.block.height,
+        &owner_addr,
         &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        amount,
+    )?;
+    let net_amount = amount - fee;
+    balances().update(
+        deps.storage,
+        &rcpt_addr,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + net_amount) },
     )?;
 
-    let attrs = vec![
+    let mut attrs = vec![
         attr("action", "send_from"),
         attr("from", &owner),
         attr("to", &contract),
         attr("by", &info.sender),
         attr("amount", amount),
     ];
+    attrs.extend(fee_attributes(amount, fee));
+    attrs.extend(memo_attributes(memo.as_deref()));
+    let events = vec![
+        SendEvent {
+            from: owner_addr.to_string(),
+            to: rcpt_addr.to_string(),
+            amount,
+            by: Some(info.sender.to_string()),
+            memo: memo.clone(),
+        }
+        .into_event()
+        .add_attributes(fee_attributes(amount, fee)),
+        allowance_changed(&owner_addr, &info.sender, &allowance),
+    ];
 
     // create a send message
     let msg = Cw20ReceiveMsg {
         sender: info.sender.into(),
-        amount,
+        amount: net_amount,
         msg,
+        memo,
     }
     .into_cosmos_msg(contract)?;
 
-    let res = Response::new().add_message(msg).add_attributes(attrs);
+    let res = Response::new()
+        .add_message(msg)
+        .add_attributes(attrs)
+        .add_events(events);
     Ok(res)
 }
 
-pub fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
+pub fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<PermitNonceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let nonce = PERMIT_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default();
+    Ok(PermitNonceResponse { nonce })
+}
+
+pub fn query_allowance(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    spender: String,
+) -> StdResult<AllowanceResponse> {
     let owner_addr = deps.api.addr_validate(&owner)?;
     let spender_addr = deps.api.addr_validate(&spender)?;
-    let allowance = ALLOWANCES
+    let mut allowance = ALLOWANCES
         .may_load(deps.storage, (&owner_addr, &spender_addr))?
         .unwrap_or_default();
+    refill_allowance(&mut allowance, &env.block);
     Ok(allowance)
 }
 
@@ -256,15 +710,26 @@ pub fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<
 mod tests {
     use super::*;
 
-    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, CosmosMsg, SubMsg, Timestamp, WasmMsg};
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coins, Api, CosmosMsg, OwnedDeps, RecoverPubkeyError, SubMsg, Timestamp, VerificationError,
+        WasmMsg,
+    };
     use cw20::{Cw20Coin, TokenInfoResponse};
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::{Signature, SigningKey};
+    use std::marker::PhantomData;
 
     use crate::contract::{execute, instantiate, query_balance, query_token_info};
+    use crate::enumerable::{query_owner_allowances, query_spender_allowances};
     use crate::msg::{ExecuteMsg, InstantiateMsg};
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
-        query_balance(deps, address.into()).unwrap().balance
+        query_balance(deps, mock_env(), address.into())
+            .unwrap()
+            .balance
     }
 
     // this will set up the instantiation for other tests
@@ -283,6 +748,7 @@ mod tests {
             }],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -301,7 +767,8 @@ mod tests {
         do_instantiate(deps.as_mut(), owner.clone(), Uint128::new(12340000));
 
         // no allowance to start
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(allowance, AllowanceResponse::default());
 
         // set allowance with height expiration
@@ -315,12 +782,16 @@ mod tests {
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow1,
-                expires
+                expires,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
 
@@ -333,12 +804,16 @@ mod tests {
             expires: None,
         };
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow2,
-                expires
+                expires,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
 
@@ -352,12 +827,16 @@ mod tests {
             expires: Some(new_expire),
         };
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow3,
-                expires: new_expire
+                expires: new_expire,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
 
@@ -368,7 +847,7 @@ mod tests {
             expires: None,
         };
         execute(deps.as_mut(), env, info, msg).unwrap();
-        let allowance = query_allowance(deps.as_ref(), owner, spender).unwrap();
+        let allowance = query_allowance(deps.as_ref(), mock_env(), owner, spender).unwrap();
         assert_eq!(allowance, AllowanceResponse::default());
     }
 
@@ -385,15 +864,15 @@ mod tests {
 
         // no allowance to start
         assert_eq!(
-            query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap(),
             AllowanceResponse::default()
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), owner.clone(), spender2.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender2.clone()).unwrap(),
             AllowanceResponse::default()
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), spender.clone(), spender2.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), spender.clone(), spender2.clone()).unwrap(),
             AllowanceResponse::default()
         );
 
@@ -420,21 +899,27 @@ mod tests {
         let expect_one = AllowanceResponse {
             allowance: allow1,
             expires,
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         let expect_two = AllowanceResponse {
             allowance: allow2,
             expires: Expiration::Never {},
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         assert_eq!(
-            query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap(),
             expect_one
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), owner.clone(), spender2.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender2.clone()).unwrap(),
             expect_two
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), spender.clone(), spender2.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), spender.clone(), spender2.clone()).unwrap(),
             AllowanceResponse::default()
         );
 
@@ -452,17 +937,20 @@ mod tests {
         let expect_three = AllowanceResponse {
             allowance: allow3,
             expires: expires3,
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         assert_eq!(
-            query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap(),
             expect_one
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), owner, spender2.clone()).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), owner, spender2.clone()).unwrap(),
             expect_two
         );
         assert_eq!(
-            query_allowance(deps.as_ref(), spender, spender2).unwrap(),
+            query_allowance(deps.as_ref(), mock_env(), spender, spender2).unwrap(),
             expect_three
         );
     }
@@ -522,6 +1010,7 @@ mod tests {
             owner: owner.clone(),
             recipient: rcpt.clone(),
             amount: transfer,
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let env = mock_env();
@@ -536,10 +1025,14 @@ mod tests {
         assert_eq!(get_balance(deps.as_ref(), rcpt.clone()), transfer);
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         let expect = AllowanceResponse {
             allowance: allow1.checked_sub(transfer).unwrap(),
             expires: Expiration::Never {},
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         assert_eq!(expect, allowance);
 
@@ -548,6 +1041,7 @@ mod tests {
             owner: owner.clone(),
             recipient: rcpt.clone(),
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let env = mock_env();
@@ -571,6 +1065,7 @@ mod tests {
             owner,
             recipient: rcpt,
             amount: Uint128::new(33443),
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
@@ -615,10 +1110,14 @@ mod tests {
         );
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         let expect = AllowanceResponse {
             allowance: allow1.checked_sub(transfer).unwrap(),
             expires: Expiration::Never {},
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         assert_eq!(expect, allowance);
 
@@ -655,6 +1154,120 @@ mod tests {
         assert_eq!(err, ContractError::Expired {});
     }
 
+    #[test]
+    fn burn_from_batch_burns_for_every_owner() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        do_instantiate(deps.as_mut(), "owner1", Uint128::new(1000));
+        let msg = ExecuteMsg::Transfer {
+            recipient: "owner2".into(),
+            amount: Uint128::new(400),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), msg).unwrap();
+        for owner in ["owner1", "owner2"] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: "spender".into(),
+                amount: Uint128::new(300),
+                expires: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(owner, &[]), msg).unwrap();
+        }
+
+        let burns = |amount2| ExecuteMsg::BurnFromBatch {
+            burns: vec![
+                Cw20Coin {
+                    address: "owner1".into(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: "owner2".into(),
+                    amount: Uint128::new(amount2),
+                },
+            ],
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("spender", &[]),
+            burns(300),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "burn_from_batch"),
+                attr("by", "spender"),
+                attr("amount", "400"),
+            ]
+        );
+        let burnt: Vec<_> = res
+            .events
+            .iter()
+            .filter_map(|event| BurnEvent::try_from(event).ok())
+            .collect();
+        assert_eq!(
+            burnt,
+            vec![
+                BurnEvent {
+                    from: "owner1".into(),
+                    amount: Uint128::new(100),
+                    by: Some("spender".into()),
+                    memo: None,
+                },
+                BurnEvent {
+                    from: "owner2".into(),
+                    amount: Uint128::new(300),
+                    by: Some("spender".into()),
+                    memo: None,
+                },
+            ]
+        );
+        // owner2's allowance is used up
+        assert_eq!(
+            AllowanceChangedEvent::try_from(&res.events[3]).unwrap(),
+            AllowanceChangedEvent {
+                owner: "owner2".into(),
+                spender: "spender".into(),
+                allowance: Uint128::zero(),
+                expires: Expiration::Never {},
+            }
+        );
+        assert_eq!(get_balance(deps.as_ref(), "owner1"), Uint128::new(500));
+        assert_eq!(get_balance(deps.as_ref(), "owner2"), Uint128::new(100));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(600)
+        );
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), "owner1".into(), "spender".into()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(200));
+
+        // one entry over its allowance fails the whole batch
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("spender", &[]),
+            burns(1),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoAllowance {});
+
+        let msg = ExecuteMsg::BurnFromBatch {
+            burns: vec![
+                Cw20Coin {
+                    address: "owner1".into(),
+                    amount: Uint128::new(1),
+                },
+                Cw20Coin {
+                    address: "owner1".into(),
+                    amount: Uint128::new(1),
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateBatchAddresses {});
+    }
+
     #[test]
     fn send_from_respects_limits() {
         let mut deps = mock_dependencies_with_balance(&[]);
@@ -684,6 +1297,7 @@ mod tests {
             amount: transfer,
             contract: contract.clone(),
             msg: send_msg.clone(),
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let env = mock_env();
@@ -696,6 +1310,7 @@ mod tests {
             sender: spender.clone(),
             amount: transfer,
             msg: send_msg.clone(),
+            memo: None,
         }
         .into_binary()
         .unwrap();
@@ -716,10 +1331,14 @@ mod tests {
         assert_eq!(get_balance(deps.as_ref(), contract.clone()), transfer);
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         let expect = AllowanceResponse {
             allowance: allow1.checked_sub(transfer).unwrap(),
             expires: Expiration::Never {},
+            recurring: None,
+            next_reset: None,
+            recipients: None,
         };
         assert_eq!(expect, allowance);
 
@@ -729,6 +1348,7 @@ mod tests {
             amount: Uint128::new(33443),
             contract: contract.clone(),
             msg: send_msg.clone(),
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let env = mock_env();
@@ -754,6 +1374,7 @@ mod tests {
             amount: Uint128::new(33443),
             contract,
             msg: send_msg,
+            memo: None,
         };
         let info = mock_info(spender.as_ref(), &[]);
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
@@ -810,12 +1431,16 @@ mod tests {
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow,
-                expires
+                expires,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
 
@@ -831,12 +1456,16 @@ mod tests {
         execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), owner.clone(), spender.clone()).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow + allow, // we increased twice
-                expires
+                expires,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
 
@@ -867,13 +1496,614 @@ mod tests {
         execute(deps.as_mut(), env, info, msg).unwrap();
 
         // ensure it looks good
-        let allowance = query_allowance(deps.as_ref(), owner, spender).unwrap();
+        let allowance = query_allowance(deps.as_ref(), mock_env(), owner, spender).unwrap();
         assert_eq!(
             allowance,
             AllowanceResponse {
                 allowance: allow,
-                expires
+                expires,
+                recurring: None,
+                next_reset: None,
+                recipients: None,
+            }
+        );
+    }
+
+    #[test]
+    fn increase_allowance_and_call_notifies_spender() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+
+        let call_msg = Binary::from(r#"{"subscribe":{}}"#.as_bytes());
+        let msg = ExecuteMsg::IncreaseAllowanceAndCall {
+            spender: "router".into(),
+            amount: Uint128::new(300),
+            expires: Some(Expiration::AtHeight(123456)),
+            msg: call_msg.clone(),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes[0],
+            attr("action", "increase_allowance_and_call")
+        );
+
+        let binary_msg = Cw20ApprovalReceiveMsg {
+            owner: "owner".into(),
+            amount: Uint128::new(300),
+            msg: call_msg,
+        }
+        .into_binary()
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router".into(),
+                msg: binary_msg,
+                funds: vec![],
+            }))]
+        );
+
+        // the allowance is in place when the spender handles the message
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), "owner".into(), "router".into()).unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(300),
+                expires: Expiration::AtHeight(123456),
+                recurring: None,
+                next_reset: None,
+                recipients: None,
             }
         );
+
+        // same rules as a plain increase
+        let msg = ExecuteMsg::IncreaseAllowanceAndCall {
+            spender: "owner".into(),
+            amount: Uint128::new(300),
+            expires: None,
+            msg: Binary::default(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotSetOwnAccount {});
+    }
+
+    const WEEK: u64 = 7 * 24 * 60 * 60;
+
+    fn env_after(seconds: u64) -> Env {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        env
+    }
+
+    fn transfer_from(
+        deps: DepsMut,
+        env: Env,
+        recipient: &str,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::TransferFrom {
+            owner: "owner".into(),
+            recipient: recipient.into(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        execute(deps, env, mock_info("spender", &[]), msg)
+    }
+
+    #[test]
+    fn recurring_allowance_refills_every_period() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+        let start = mock_env().block.time;
+
+        let msg = ExecuteMsg::SetAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+            period: Some(WEEK),
+            recipients: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        transfer_from(deps.as_mut(), env_after(10), "shop", 60).unwrap();
+        let err = transfer_from(deps.as_mut(), env_after(20), "shop", 50).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        let allowance = query_allowance(
+            deps.as_ref(),
+            env_after(20),
+            "owner".into(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(40),
+                expires: Expiration::Never {},
+                recurring: Some(RecurringAllowance {
+                    amount: Uint128::new(100),
+                    period: WEEK,
+                }),
+                next_reset: Some(start.plus_seconds(WEEK)),
+                recipients: None,
+            }
+        );
+
+        // more than a period later, the allowance is full again and the periods keep their rhythm
+        let later = env_after(WEEK + 100);
+        let allowances = query_owner_allowances(
+            deps.as_ref(),
+            later.clone(),
+            "owner".into(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(allowances.allowances[0].allowance, Uint128::new(100));
+        assert_eq!(
+            allowances.allowances[0].next_reset,
+            Some(start.plus_seconds(2 * WEEK))
+        );
+        transfer_from(deps.as_mut(), later, "shop", 100).unwrap();
+        let err = transfer_from(deps.as_mut(), env_after(WEEK + 200), "shop", 1).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        // several skipped periods do not add up
+        let allowance = query_allowance(
+            deps.as_ref(),
+            env_after(5 * WEEK),
+            "owner".into(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(100));
+        assert_eq!(allowance.next_reset, Some(start.plus_seconds(6 * WEEK)));
+        assert_eq!(get_balance(deps.as_ref(), "shop"), Uint128::new(160));
+
+        let msg = ExecuteMsg::SetAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+            period: Some(0),
+            recipients: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAllowancePeriod {});
+    }
+
+    #[test]
+    fn decreasing_recurring_allowance_keeps_it() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+        let start = mock_env().block.time;
+
+        let msg = ExecuteMsg::SetAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+            period: Some(WEEK),
+            recipients: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        transfer_from(deps.as_mut(), env_after(10), "shop", 30).unwrap();
+
+        // decreasing by more than is left only empties the current period
+        let msg = ExecuteMsg::DecreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(500),
+            expires: None,
+        };
+        execute(deps.as_mut(), env_after(20), mock_info("owner", &[]), msg).unwrap();
+        let allowance = query_allowance(
+            deps.as_ref(),
+            env_after(20),
+            "owner".into(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::zero(),
+                expires: Expiration::Never {},
+                recurring: Some(RecurringAllowance {
+                    amount: Uint128::new(100),
+                    period: WEEK,
+                }),
+                next_reset: Some(start.plus_seconds(WEEK)),
+                recipients: None,
+            }
+        );
+        let err = transfer_from(deps.as_mut(), env_after(30), "shop", 1).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        // the next period refills it
+        transfer_from(deps.as_mut(), env_after(WEEK + 10), "shop", 100).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "shop"), Uint128::new(130));
+    }
+
+    #[test]
+    fn scoped_allowance_limits_recipients() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+
+        let msg = ExecuteMsg::SetAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+            period: None,
+            recipients: Some(vec!["shop".into(), "contract".into()]),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        transfer_from(deps.as_mut(), mock_env(), "shop", 10).unwrap();
+        let err = transfer_from(deps.as_mut(), mock_env(), "thief", 10).unwrap_err();
+        assert_eq!(err, ContractError::RecipientNotAllowed {});
+
+        let msg = ExecuteMsg::SendFrom {
+            owner: "owner".into(),
+            contract: "contract".into(),
+            amount: Uint128::new(10),
+            msg: Binary::default(),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+
+        // burning is not moving tokens to an allowed recipient
+        let msg = ExecuteMsg::BurnFrom {
+            owner: "owner".into(),
+            amount: Uint128::new(10),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::RecipientNotAllowed {});
+
+        // increasing keeps the scope
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(20),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), "owner".into(), "spender".into()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(100));
+        assert_eq!(
+            allowance.recipients,
+            Some(vec!["shop".to_string(), "contract".to_string()])
+        );
+    }
+
+    #[test]
+    fn used_up_allowance_is_removed() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        transfer_from(deps.as_mut(), mock_env(), "shop", 60).unwrap();
+        transfer_from(deps.as_mut(), mock_env(), "shop", 40).unwrap();
+
+        let err = transfer_from(deps.as_mut(), mock_env(), "shop", 1).unwrap_err();
+        assert_eq!(err, ContractError::NoAllowance {});
+        let allowances =
+            query_owner_allowances(deps.as_ref(), mock_env(), "owner".into(), None, None, None)
+                .unwrap();
+        assert_eq!(allowances.allowances, vec![]);
+
+        // a recurring allowance stays around to be refilled
+        let msg = ExecuteMsg::SetAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(100),
+            expires: None,
+            period: Some(WEEK),
+            recipients: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        transfer_from(deps.as_mut(), mock_env(), "shop", 100).unwrap();
+        let allowance = query_allowance(
+            deps.as_ref(),
+            env_after(WEEK),
+            "owner".into(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(100));
+    }
+
+    #[test]
+    fn prune_expired_allowances() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+        let height = mock_env().block.height;
+
+        for (spender, expires) in [
+            ("spender1", Expiration::AtHeight(height + 10)),
+            ("spender2", Expiration::Never {}),
+            ("spender3", Expiration::AtHeight(height + 20)),
+            ("spender4", Expiration::AtHeight(height + 30)),
+        ] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.into(),
+                amount: Uint128::new(100),
+                expires: Some(expires),
+            };
+            execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+        }
+
+        // anyone can prune, and only expired allowances go
+        let mut env = mock_env();
+        env.block.height += 25;
+        let msg = ExecuteMsg::PruneExpiredAllowances {
+            owner: "owner".into(),
+            limit: Some(1),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "prune_expired_allowances"),
+                attr("owner", "owner"),
+                attr("pruned", "1"),
+            ]
+        );
+        let msg = ExecuteMsg::PruneExpiredAllowances {
+            owner: "owner".into(),
+            limit: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(res.attributes[2], attr("pruned", "1"));
+
+        let allowances =
+            query_owner_allowances(deps.as_ref(), env, "owner".into(), None, None, Some(true))
+                .unwrap();
+        let spenders: Vec<_> = allowances
+            .allowances
+            .into_iter()
+            .map(|a| a.spender)
+            .collect();
+        assert_eq!(spenders, vec!["spender2", "spender4"]);
+        let allowances = query_spender_allowances(
+            deps.as_ref(),
+            mock_env(),
+            "spender1".into(),
+            None,
+            None,
+            Some(true),
+        )
+        .unwrap();
+        assert_eq!(allowances.allowances, vec![]);
+    }
+
+    /// MockApi cannot humanize the 20 byte canonical addresses derived from public keys,
+    /// so this gives them a readable form and leaves everything else to MockApi.
+    #[derive(Default)]
+    struct PermitApi(MockApi);
+
+    impl Api for PermitApi {
+        fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+            self.0.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+            self.0.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+            if canonical.len() != 20 {
+                return self.0.addr_humanize(canonical);
+            }
+            let hex: String = canonical
+                .as_slice()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            Ok(Addr::unchecked(format!("signer{}", hex)))
+        }
+
+        fn secp256k1_verify(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            recovery_param: u8,
+        ) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.0
+                .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(
+            &self,
+            messages: &[&[u8]],
+            signatures: &[&[u8]],
+            public_keys: &[&[u8]],
+        ) -> Result<bool, VerificationError> {
+            self.0
+                .ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message: &str) {
+            self.0.debug(message)
+        }
+    }
+
+    fn permit_dependencies() -> OwnedDeps<MockStorage, PermitApi, MockQuerier> {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: PermitApi::default(),
+            querier: MockQuerier::default(),
+            custom_query_type: PhantomData,
+        }
+    }
+
+    struct Permit {
+        key: SigningKey,
+        payload: PermitPayload,
+    }
+
+    impl Permit {
+        fn new(spender: &str, amount: u128, nonce: u64) -> Self {
+            let env = mock_env();
+            Permit {
+                key: SigningKey::from_bytes(&[7; 32]).unwrap(),
+                payload: PermitPayload {
+                    chain_id: env.block.chain_id,
+                    contract: env.contract.address.into(),
+                    spender: spender.into(),
+                    amount: Uint128::new(amount),
+                    expires: None,
+                    nonce,
+                },
+            }
+        }
+
+        fn pubkey(&self) -> Binary {
+            Binary::from(self.key.verifying_key().to_bytes().as_slice())
+        }
+
+        fn owner(&self) -> Addr {
+            let hash = Ripemd160::digest(Sha256::digest(self.pubkey().as_slice()));
+            PermitApi::default()
+                .addr_humanize(&CanonicalAddr::from(hash.as_slice()))
+                .unwrap()
+        }
+
+        fn sign(&self) -> Binary {
+            let signature: Signature = self.key.sign(&to_vec(&self.payload).unwrap());
+            Binary::from(signature.as_ref())
+        }
+
+        fn msg(&self) -> ExecuteMsg {
+            ExecuteMsg::Permit {
+                owner_pubkey: self.pubkey(),
+                spender: self.payload.spender.clone(),
+                amount: self.payload.amount,
+                expires: self.payload.expires,
+                nonce: self.payload.nonce,
+                signature: self.sign(),
+            }
+        }
+    }
+
+    #[test]
+    fn permit_grants_allowance() {
+        let mut deps = permit_dependencies();
+        let permit = Permit::new("spender", 300, 0);
+        let owner = permit.owner();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(1000));
+
+        // anyone can relay the signed permit
+        let relayer = mock_info("relayer", &[]);
+        let res = execute(deps.as_mut(), mock_env(), relayer.clone(), permit.msg()).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "permit"),
+                attr("owner", owner.as_str()),
+                attr("spender", "spender"),
+                attr("amount", "300"),
+                attr("nonce", "0"),
+                attr("relayer", "relayer"),
+            ]
+        );
+        let allowance = query_allowance(
+            deps.as_ref(),
+            mock_env(),
+            owner.to_string(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(300));
+        assert_eq!(
+            query_permit_nonce(deps.as_ref(), owner.to_string()).unwrap(),
+            PermitNonceResponse { nonce: 1 }
+        );
+
+        // the spender can use it right away
+        let msg = ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: "rcpt".into(),
+            amount: Uint128::new(300),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "rcpt"), Uint128::new(300));
+
+        // a permit cannot be replayed
+        let err = execute(deps.as_mut(), mock_env(), relayer.clone(), permit.msg()).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitNonce { expected: 1 });
+        let permit = Permit::new("spender", 50, 1);
+        execute(deps.as_mut(), mock_env(), relayer, permit.msg()).unwrap();
+        let allowance = query_allowance(
+            deps.as_ref(),
+            mock_env(),
+            owner.to_string(),
+            "spender".into(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(50));
+    }
+
+    #[test]
+    fn permit_rejects_invalid_signatures() {
+        let mut deps = permit_dependencies();
+        let permit = Permit::new("spender", 300, 0);
+        do_instantiate(deps.as_mut(), permit.owner(), Uint128::new(1000));
+        let relayer = mock_info("relayer", &[]);
+
+        // the amount was tampered with
+        let mut msg = permit.msg();
+        if let ExecuteMsg::Permit { amount, .. } = &mut msg {
+            *amount = Uint128::new(3000);
+        }
+        let err = execute(deps.as_mut(), mock_env(), relayer.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitSignature {});
+
+        // signed for another chain
+        let mut other_chain = Permit::new("spender", 300, 0);
+        other_chain.payload.chain_id = "other-chain".into();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            relayer.clone(),
+            other_chain.msg(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitSignature {});
+
+        // uncompressed keys would derive another address
+        let mut msg = permit.msg();
+        if let ExecuteMsg::Permit { owner_pubkey, .. } = &mut msg {
+            *owner_pubkey = Binary::from([4; 65]);
+        }
+        let err = execute(deps.as_mut(), mock_env(), relayer, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermitPubkey {});
+
+        assert_eq!(
+            query_permit_nonce(deps.as_ref(), permit.owner().into()).unwrap(),
+            PermitNonceResponse { nonce: 0 }
+        );
     }
 }
@@ -0,0 +1,321 @@
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw20::events::{ForceBurnEvent, ForceTransferEvent};
+use cw20::ClawbackAdminResponse;
+
+use crate::compliance::ensure_compliant;
+use crate::error::ContractError;
+use crate::state::{balances, CLAWBACK_ADMIN, TOKEN_INFO};
+
+fn ensure_clawback_admin(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let admin = CLAWBACK_ADMIN
+        .may_load(storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if admin != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Moves tokens out of any account. This deliberately ignores allowances, vesting locks, the
+/// transfer fee and a pause, so a recovery can never be blocked by the account it targets.
+/// Only the recipient is checked for compliance, as with mints.
+pub fn execute_force_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: String,
+    to: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_clawback_admin(deps.storage, &info.sender)?;
+    if amount == Uint128::zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let from_addr = deps.api.addr_validate(&from)?;
+    let to_addr = deps.api.addr_validate(&to)?;
+    ensure_compliant(deps.storage, None, &to_addr)?;
+
+    balances().update(
+        deps.storage,
+        &from_addr,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    balances().update(
+        deps.storage,
+        &to_addr,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("action", "force_transfer")
+        .add_attribute("admin", &info.sender)
+        .add_attribute("from", &from)
+        .add_attribute("to", &to)
+        .add_attribute("amount", amount)
+        .add_event(
+            ForceTransferEvent {
+                admin: info.sender.into(),
+                from,
+                to,
+                amount,
+            }
+            .into_event(),
+        );
+    Ok(res)
+}
+
+/// Destroys tokens of any account, bypassing the same checks as `execute_force_transfer`.
+pub fn execute_force_burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_clawback_admin(deps.storage, &info.sender)?;
+    if amount == Uint128::zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let from_addr = deps.api.addr_validate(&from)?;
+    balances().update(
+        deps.storage,
+        &from_addr,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(amount)?)
+        },
+    )?;
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    config.total_supply = config
+        .total_supply
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
+
+    let res = Response::new()
+        .add_attribute("action", "force_burn")
+        .add_attribute("admin", &info.sender)
+        .add_attribute("from", &from)
+        .add_attribute("amount", amount)
+        .add_event(
+            ForceBurnEvent {
+                admin: info.sender.into(),
+                from,
+                amount,
+            }
+            .into_event(),
+        );
+    Ok(res)
+}
+
+pub fn execute_renounce_clawback_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_clawback_admin(deps.storage, &info.sender)?;
+    CLAWBACK_ADMIN.remove(deps.storage);
+
+    let res = Response::new()
+        .add_attribute("action", "renounce_clawback_admin")
+        .add_attribute("admin", info.sender);
+    Ok(res)
+}
+
+pub fn query_clawback_admin(deps: Deps) -> StdResult<ClawbackAdminResponse> {
+    let admin = CLAWBACK_ADMIN.may_load(deps.storage)?.map(Into::into);
+    Ok(ClawbackAdminResponse { admin })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Attribute, Timestamp};
+    use cw20::{Cw20Coin, VestingSchedule};
+
+    use crate::contract::{execute, query_token_info};
+    use crate::msg::{ExecuteMsg, InitialVesting, InstantiateMsg};
+    use crate::test_helpers::{get_balance, instantiate_token, token_msg, OWNER};
+
+    const ADMIN: &str = "clawback";
+    const PAUSER: &str = "pauser";
+
+    fn do_instantiate(deps: DepsMut) {
+        let msg = InstantiateMsg {
+            initial_balances: vec![
+                Cw20Coin {
+                    address: OWNER.into(),
+                    amount: Uint128::new(1000),
+                },
+                Cw20Coin {
+                    address: "team".into(),
+                    amount: Uint128::new(500),
+                },
+            ],
+            initial_vesting: Some(vec![InitialVesting {
+                address: "team".into(),
+                schedule: VestingSchedule::Cliff {
+                    time: mock_env().block.time.plus_seconds(1000),
+                },
+            }]),
+            pauser: Some(PAUSER.into()),
+            clawback_admin: Some(ADMIN.into()),
+            ..token_msg()
+        };
+        instantiate_token(deps, mock_env(), msg);
+    }
+
+    #[test]
+    fn force_transfer_works() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ForceTransfer {
+            from: OWNER.into(),
+            to: "recovery".into(),
+            amount: Uint128::new(300),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // works while paused and without any allowance
+        let pause = ExecuteMsg::Pause {};
+        execute(deps.as_mut(), mock_env(), mock_info(PAUSER, &[]), pause).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                Attribute::new("action", "force_transfer"),
+                Attribute::new("admin", ADMIN),
+                Attribute::new("from", OWNER),
+                Attribute::new("to", "recovery"),
+                Attribute::new("amount", "300"),
+            ]
+        );
+        // a dedicated event, not a transfer
+        assert_eq!(
+            res.events,
+            vec![ForceTransferEvent {
+                admin: ADMIN.into(),
+                from: OWNER.into(),
+                to: "recovery".into(),
+                amount: Uint128::new(300),
+            }
+            .into_event()]
+        );
+        assert_eq!(get_balance(deps.as_ref(), OWNER), Uint128::new(700));
+        assert_eq!(get_balance(deps.as_ref(), "recovery"), Uint128::new(300));
+
+        // locked tokens can be recovered as well
+        let msg = ExecuteMsg::ForceTransfer {
+            from: "team".into(),
+            to: "recovery".into(),
+            amount: Uint128::new(500),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "team"), Uint128::zero());
+
+        // but not more than the balance
+        let msg = ExecuteMsg::ForceTransfer {
+            from: OWNER.into(),
+            to: "recovery".into(),
+            amount: Uint128::new(701),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    }
+
+    #[test]
+    fn force_burn_works() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+
+        let msg = ExecuteMsg::ForceBurn {
+            from: "team".into(),
+            amount: Uint128::new(200),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(res.attributes[0], Attribute::new("action", "force_burn"));
+        assert_eq!(
+            res.events,
+            vec![ForceBurnEvent {
+                admin: ADMIN.into(),
+                from: "team".into(),
+                amount: Uint128::new(200),
+            }
+            .into_event()]
+        );
+        assert_eq!(get_balance(deps.as_ref(), "team"), Uint128::new(300));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(1300)
+        );
+
+        // the rest stays locked for the owner
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(env.block.time.seconds() + 10);
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), env, mock_info("team", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+    }
+
+    #[test]
+    fn renounce_is_permanent() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut());
+        assert_eq!(
+            query_clawback_admin(deps.as_ref()).unwrap().admin,
+            Some(ADMIN.to_string())
+        );
+
+        let msg = ExecuteMsg::RenounceClawbackAdmin {};
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(
+            query_clawback_admin(deps.as_ref()).unwrap(),
+            ClawbackAdminResponse { admin: None }
+        );
+
+        let msg = ExecuteMsg::ForceBurn {
+            from: OWNER.into(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+}
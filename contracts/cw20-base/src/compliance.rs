@@ -0,0 +1,319 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw20::{AddressListResponse, ComplianceInfoResponse, ComplianceMode};
+use cw_storage_plus::{Bound, Map};
+
+use crate::error::ContractError;
+use crate::state::{ALLOWLIST, BLOCKLIST, COMPLIANCE_INFO};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// Checks both parties of a token movement against the blocklist or allowlist, depending
+/// on the compliance mode. `from` is None for mints. Does nothing if compliance checks
+/// were not enabled on instantiation.
+pub fn ensure_compliant(
+    storage: &dyn Storage,
+    from: Option<&Addr>,
+    to: &Addr,
+) -> Result<(), ContractError> {
+    let compliance = match COMPLIANCE_INFO.may_load(storage)? {
+        Some(compliance) => compliance,
+        None => return Ok(()),
+    };
+
+    for addr in from.into_iter().chain(Some(to)) {
+        let allowed = match compliance.mode {
+            ComplianceMode::Blocklist => !BLOCKLIST.has(storage, addr),
+            ComplianceMode::Allowlist => ALLOWLIST.has(storage, addr),
+        };
+        if !allowed {
+            return Err(ContractError::NotCompliant {
+                address: addr.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_update_blocklist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    update_address_list(deps, &info, &BLOCKLIST, add, remove)?;
+    Ok(Response::new().add_attribute("action", "update_blocklist"))
+}
+
+pub fn execute_update_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    update_address_list(deps, &info, &ALLOWLIST, add, remove)?;
+    Ok(Response::new().add_attribute("action", "update_allowlist"))
+}
+
+fn update_address_list(
+    deps: DepsMut,
+    info: &MessageInfo,
+    list: &Map<&Addr, ()>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<(), ContractError> {
+    let compliance = COMPLIANCE_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if compliance.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for addr in add {
+        let addr = deps.api.addr_validate(&addr)?;
+        list.save(deps.storage, &addr, &())?;
+    }
+    for addr in remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        list.remove(deps.storage, &addr);
+    }
+    Ok(())
+}
+
+pub fn query_compliance_info(deps: Deps) -> StdResult<Option<ComplianceInfoResponse>> {
+    let compliance = COMPLIANCE_INFO
+        .may_load(deps.storage)?
+        .map(|c| ComplianceInfoResponse {
+            admin: c.admin.into(),
+            mode: c.mode,
+        });
+    Ok(compliance)
+}
+
+pub fn query_blocklist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AddressListResponse> {
+    query_address_list(deps, &BLOCKLIST, start_after, limit)
+}
+
+pub fn query_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AddressListResponse> {
+    query_address_list(deps, &ALLOWLIST, start_after, limit)
+}
+
+fn query_address_list(
+    deps: Deps,
+    list: &Map<&Addr, ()>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AddressListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+
+    let addresses = list
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<_>>()?;
+    Ok(AddressListResponse { addresses })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Binary, Uint128};
+    use cw20::MinterResponse;
+
+    use crate::contract::execute;
+    use crate::msg::{ExecuteMsg, InstantiateComplianceInfo, InstantiateMsg};
+    use crate::test_helpers::{get_balance, instantiate_token, token_msg, OWNER};
+
+    const ADMIN: &str = "compliance";
+    const MINTER: &str = "minter";
+
+    fn do_instantiate(deps: DepsMut, mode: ComplianceMode) {
+        let msg = InstantiateMsg {
+            mint: Some(MinterResponse {
+                minter: MINTER.into(),
+                cap: None,
+            }),
+            compliance: Some(InstantiateComplianceInfo {
+                admin: ADMIN.into(),
+                mode,
+            }),
+            ..token_msg()
+        };
+        instantiate_token(deps, mock_env(), msg);
+    }
+
+    fn transfer(deps: DepsMut, from: &str, to: &str) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::Transfer {
+            recipient: to.into(),
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        execute(deps, mock_env(), mock_info(from, &[]), msg)
+    }
+
+    fn not_compliant(address: &str) -> ContractError {
+        ContractError::NotCompliant {
+            address: address.into(),
+        }
+    }
+
+    #[test]
+    fn only_admin_updates_lists() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), ComplianceMode::Blocklist);
+
+        let msg = ExecuteMsg::UpdateBlocklist {
+            add: vec!["bad".into()],
+            remove: vec![],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::UpdateAllowlist {
+            add: vec!["good".into()],
+            remove: vec![],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        assert_eq!(
+            query_compliance_info(deps.as_ref()).unwrap(),
+            Some(ComplianceInfoResponse {
+                admin: ADMIN.into(),
+                mode: ComplianceMode::Blocklist,
+            })
+        );
+        assert_eq!(
+            query_blocklist(deps.as_ref(), None, None).unwrap(),
+            AddressListResponse::default()
+        );
+    }
+
+    #[test]
+    fn blocklist_mode() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), ComplianceMode::Blocklist);
+
+        // anyone can receive tokens by default
+        transfer(deps.as_mut(), OWNER, "bad").unwrap();
+
+        let msg = ExecuteMsg::UpdateBlocklist {
+            add: vec!["bad".into(), "worse".into()],
+            remove: vec![],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(
+            query_blocklist(deps.as_ref(), None, None)
+                .unwrap()
+                .addresses,
+            vec!["bad".to_string(), "worse".to_string()]
+        );
+
+        // blocked addresses can neither send nor receive
+        let err = transfer(deps.as_mut(), OWNER, "bad").unwrap_err();
+        assert_eq!(err, not_compliant("bad"));
+        let err = transfer(deps.as_mut(), "bad", OWNER).unwrap_err();
+        assert_eq!(err, not_compliant("bad"));
+
+        let msg = ExecuteMsg::Send {
+            contract: "worse".into(),
+            amount: Uint128::new(1),
+            msg: Binary::default(),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+        assert_eq!(err, not_compliant("worse"));
+
+        let msg = ExecuteMsg::Mint {
+            recipient: "bad".into(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg).unwrap_err();
+        assert_eq!(err, not_compliant("bad"));
+
+        // allowances do not help either
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(10),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        let msg = ExecuteMsg::TransferFrom {
+            owner: OWNER.into(),
+            recipient: "bad".into(),
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err();
+        assert_eq!(err, not_compliant("bad"));
+        let msg = ExecuteMsg::SendFrom {
+            owner: OWNER.into(),
+            contract: "worse".into(),
+            amount: Uint128::new(1),
+            msg: Binary::default(),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err();
+        assert_eq!(err, not_compliant("worse"));
+
+        // removing from the blocklist restores access
+        let msg = ExecuteMsg::UpdateBlocklist {
+            add: vec![],
+            remove: vec!["bad".into()],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        transfer(deps.as_mut(), "bad", OWNER).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bad"), Uint128::zero());
+    }
+
+    #[test]
+    fn allowlist_mode() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), ComplianceMode::Allowlist);
+
+        // nobody is allowed by default
+        let err = transfer(deps.as_mut(), OWNER, "good").unwrap_err();
+        assert_eq!(err, not_compliant(OWNER));
+
+        let msg = ExecuteMsg::UpdateAllowlist {
+            add: vec![OWNER.into(), "good".into(), "better".into()],
+            remove: vec![],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+        transfer(deps.as_mut(), OWNER, "good").unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "good"), Uint128::new(1));
+        let err = transfer(deps.as_mut(), OWNER, "unknown").unwrap_err();
+        assert_eq!(err, not_compliant("unknown"));
+
+        let msg = ExecuteMsg::Mint {
+            recipient: "unknown".into(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), msg).unwrap_err();
+        assert_eq!(err, not_compliant("unknown"));
+
+        // pagination works
+        let page = query_allowlist(deps.as_ref(), None, Some(2)).unwrap();
+        assert_eq!(
+            page.addresses,
+            vec!["better".to_string(), "good".to_string()]
+        );
+        let page = query_allowlist(deps.as_ref(), Some("good".into()), Some(2)).unwrap();
+        assert_eq!(page.addresses, vec![OWNER.to_string()]);
+    }
+}
@@ -2,107 +2,100 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Storage, Uint128,
 };
 
-use cw2::set_contract_version;
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
+use cw20::events::{BurnEvent, MintEvent, SendEvent, TransferEvent};
+use cw20::interfaces;
 use cw20::{
-    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
-    MarketingInfoResponse, MinterResponse, TokenInfoResponse,
+    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, Cw20Send, DownloadLogoResponse, Logo, LogoInfo,
+    MarketingInfoResponse, MinterResponse, PauseInfoResponse, TokenInfoResponse,
 };
 use cw_utils::ensure_from_older_version;
 
+use crate::admin::{execute_update_cap, execute_update_token_info, query_token_admin};
 use crate::allowances::{
-    execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance,
+    execute_burn_from, execute_burn_from_batch, execute_decrease_allowance,
+    execute_increase_allowance, execute_increase_allowance_and_call, execute_permit,
+    execute_prune_expired_allowances, execute_send_from, execute_set_allowance,
+    execute_transfer_from, query_allowance, query_permit_nonce,
+};
+use crate::clawback::{
+    execute_force_burn, execute_force_transfer, execute_renounce_clawback_admin,
+    query_clawback_admin,
+};
+use crate::compliance::{
+    ensure_compliant, execute_update_allowlist, execute_update_blocklist, query_allowlist,
+    query_blocklist, query_compliance_info,
+};
+use crate::enumerable::{
+    query_all_accounts, query_all_balances, query_owner_allowances, query_spender_allowances,
+    query_top_holders,
 };
-use crate::enumerable::{query_all_accounts, query_owner_allowances, query_spender_allowances};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::fee::{
+    charge_transfer_fee, execute_update_fee, fee_attributes, query_fee_config, validate_fee,
+};
+use crate::memo::{ensure_valid_memo, memo_attributes};
+use crate::minters::{
+    consume_mint_allowance, ensure_registered_minter, execute_add_minter, execute_remove_minter,
+    execute_update_minter_quota, query_all_minters,
+};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, TotalSupplyResponse};
 use crate::state::{
-    MinterData, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO, MARKETING_INFO,
-    TOKEN_INFO,
+    balances, ComplianceInfo, MinterData, PauseInfo, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER,
+    CLAWBACK_ADMIN, COMPLIANCE_INFO, FEE_ADMIN, FEE_CONFIG, LOGO, MARKETING_INFO, MAX_MEMO_LENGTH,
+    MINTERS, MINT_ADMIN, PAUSE_INFO, TOKEN_ADMIN, TOKEN_INFO,
 };
+use crate::vesting::{create_vesting, ensure_spendable, query_vesting_info, spendable_balance};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-const LOGO_SIZE_CAP: usize = 5 * 1024;
-
-/// Checks if data starts with XML preamble
-fn verify_xml_preamble(data: &[u8]) -> Result<(), ContractError> {
-    // The easiest way to perform this check would be just match on regex, however regex
-    // compilation is heavy and probably not worth it.
-
-    let preamble = data
-        .split_inclusive(|c| *c == b'>')
-        .next()
-        .ok_or(ContractError::InvalidXmlPreamble {})?;
-
-    const PREFIX: &[u8] = b"<?xml ";
-    const POSTFIX: &[u8] = b"?>";
-
-    if !(preamble.starts_with(PREFIX) && preamble.ends_with(POSTFIX)) {
-        Err(ContractError::InvalidXmlPreamble {})
-    } else {
-        Ok(())
-    }
-
-    // Additionally attributes format could be validated as they are well defined, as well as
-    // comments presence inside of preable, but it is probably not worth it.
-}
-
-/// Validates XML logo
-fn verify_xml_logo(logo: &[u8]) -> Result<(), ContractError> {
-    verify_xml_preamble(logo)?;
-
-    if logo.len() > LOGO_SIZE_CAP {
-        Err(ContractError::LogoTooBig {})
-    } else {
-        Ok(())
-    }
-}
-
-/// Validates png logo
-fn verify_png_logo(logo: &[u8]) -> Result<(), ContractError> {
-    // PNG header format:
-    // 0x89 - magic byte, out of ASCII table to fail on 7-bit systems
-    // "PNG" ascii representation
-    // [0x0d, 0x0a] - dos style line ending
-    // 0x1a - dos control character, stop displaying rest of the file
-    // 0x0a - unix style line ending
-    const HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
-    if logo.len() > LOGO_SIZE_CAP {
-        Err(ContractError::LogoTooBig {})
-    } else if !logo.starts_with(&HEADER) {
-        Err(ContractError::InvalidPngHeader {})
-    } else {
-        Ok(())
-    }
-}
-
-/// Checks if passed logo is correct, and if not, returns an error
-fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
-    match logo {
-        Logo::Embedded(EmbeddedLogo::Svg(logo)) => verify_xml_logo(logo),
-        Logo::Embedded(EmbeddedLogo::Png(logo)) => verify_png_logo(logo),
-        Logo::Url(_) => Ok(()), // Any reasonable url validation would be regex based, probably not worth it
-    }
+// every part of the cw20 spec this contract implements
+const INTERFACES: &[&str] = &[
+    interfaces::BASE,
+    interfaces::BATCH,
+    interfaces::ALLOWANCE,
+    interfaces::PERMIT,
+    interfaces::MINTABLE,
+    interfaces::MINTERS,
+    interfaces::ADMIN,
+    interfaces::CLAWBACK,
+    interfaces::ENUMERABLE,
+    interfaces::MARKETING,
+    interfaces::PAUSABLE,
+    interfaces::COMPLIANCE,
+    interfaces::FEE,
+    interfaces::VESTING,
+];
+
+fn supported_interfaces() -> Vec<ContractInterface> {
+    INTERFACES
+        .iter()
+        .map(|interface| ContractInterface::new(*interface, interfaces::VERSION))
+        .collect()
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     // check valid token info
     msg.validate()?;
     // create initial accounts
-    let total_supply = create_accounts(&mut deps, &msg.initial_balances)?;
+    let total_supply = create_accounts(&mut deps, &msg.initial_balances, env.block.height)?;
+    if let Some(vesting) = &msg.initial_vesting {
+        create_vesting(&mut deps, &msg.initial_balances, vesting)?;
+    }
 
     if let Some(limit) = msg.get_cap() {
         if total_supply > limit {
@@ -126,11 +119,49 @@ pub fn instantiate(
         total_supply,
         mint,
     };
-    TOKEN_INFO.save(deps.storage, &data)?;
+    TOKEN_INFO.save(deps.storage, &data, env.block.height)?;
+
+    if let Some(mint_admin) = msg.mint_admin {
+        MINT_ADMIN.save(deps.storage, &deps.api.addr_validate(&mint_admin)?)?;
+    }
+
+    if let Some(pauser) = msg.pauser {
+        let data = PauseInfo {
+            pauser: deps.api.addr_validate(&pauser)?,
+            paused: false,
+        };
+        PAUSE_INFO.save(deps.storage, &data)?;
+    }
+
+    if let Some(compliance) = msg.compliance {
+        let data = ComplianceInfo {
+            admin: deps.api.addr_validate(&compliance.admin)?,
+            mode: compliance.mode,
+        };
+        COMPLIANCE_INFO.save(deps.storage, &data)?;
+    }
+
+    if let Some(fee) = msg.transfer_fee {
+        let data = validate_fee(deps.api, fee)?;
+        FEE_CONFIG.save(deps.storage, &data)?;
+    }
+    if let Some(fee_admin) = msg.fee_admin {
+        FEE_ADMIN.save(deps.storage, &deps.api.addr_validate(&fee_admin)?)?;
+    }
+
+    if let Some(admin) = msg.admin {
+        TOKEN_ADMIN.save(deps.storage, &deps.api.addr_validate(&admin)?)?;
+    }
+    if let Some(clawback_admin) = msg.clawback_admin {
+        CLAWBACK_ADMIN.save(deps.storage, &deps.api.addr_validate(&clawback_admin)?)?;
+    }
+    if let Some(max_memo_length) = msg.max_memo_length {
+        MAX_MEMO_LENGTH.save(deps.storage, &max_memo_length)?;
+    }
 
     if let Some(marketing) = msg.marketing {
         let logo = if let Some(logo) = marketing.logo {
-            verify_logo(&logo)?;
+            logo.validate()?;
             LOGO.save(deps.storage, &logo)?;
 
             match logo {
@@ -159,13 +190,14 @@ pub fn instantiate(
 pub fn create_accounts(
     deps: &mut DepsMut,
     accounts: &[Cw20Coin],
+    height: u64,
 ) -> Result<Uint128, ContractError> {
     validate_accounts(accounts)?;
 
     let mut total_supply = Uint128::zero();
     for row in accounts {
         let address = deps.api.addr_validate(&row.address)?;
-        BALANCES.save(deps.storage, &address, &row.amount)?;
+        balances().save(deps.storage, &address, &row.amount, height)?;
         total_supply += row.amount;
     }
 
@@ -192,38 +224,90 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Transfer { recipient, amount } => {
-            execute_transfer(deps, env, info, recipient, amount)
-        }
-        ExecuteMsg::Burn { amount } => execute_burn(deps, env, info, amount),
+        ExecuteMsg::Transfer {
+            recipient,
+            amount,
+            memo,
+        } => execute_transfer(deps, env, info, recipient, amount, memo),
+        ExecuteMsg::Burn { amount, memo } => execute_burn(deps, env, info, amount, memo),
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => execute_send(deps, env, info, contract, amount, msg),
+            memo,
+        } => execute_send(deps, env, info, contract, amount, msg, memo),
+        ExecuteMsg::TransferBatch { transfers } => {
+            execute_transfer_batch(deps, env, info, transfers)
+        }
+        ExecuteMsg::SendBatch { sends } => execute_send_batch(deps, env, info, sends),
         ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
+        ExecuteMsg::MintAndSend {
+            contract,
+            amount,
+            msg,
+        } => execute_mint_and_send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::MintBatch { mints } => execute_mint_batch(deps, env, info, mints),
+        ExecuteMsg::BurnFromBatch { burns } => execute_burn_from_batch(deps, env, info, burns),
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
             expires,
         } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::SetAllowance {
+            spender,
+            amount,
+            expires,
+            period,
+            recipients,
+        } => execute_set_allowance(
+            deps, env, info, spender, amount, expires, period, recipients,
+        ),
+        ExecuteMsg::IncreaseAllowanceAndCall {
+            spender,
+            amount,
+            expires,
+            msg,
+        } => execute_increase_allowance_and_call(deps, env, info, spender, amount, expires, msg),
         ExecuteMsg::DecreaseAllowance {
             spender,
             amount,
             expires,
         } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::Permit {
+            owner_pubkey,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+        } => execute_permit(
+            deps,
+            env,
+            info,
+            owner_pubkey,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+        ),
+        ExecuteMsg::PruneExpiredAllowances { owner, limit } => {
+            execute_prune_expired_allowances(deps, env, info, owner, limit)
+        }
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
             amount,
-        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
+            memo,
+        } => execute_transfer_from(deps, env, info, owner, recipient, amount, memo),
         ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
         ExecuteMsg::SendFrom {
             owner,
             contract,
             amount,
             msg,
-        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+            memo,
+        } => execute_send_from(deps, env, info, owner, contract, amount, msg, memo),
         ExecuteMsg::UpdateMarketing {
             project,
             description,
@@ -233,77 +317,159 @@ pub fn execute(
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
+        ExecuteMsg::Pause {} => execute_set_paused(deps, env, info, true),
+        ExecuteMsg::Unpause {} => execute_set_paused(deps, env, info, false),
+        ExecuteMsg::UpdateBlocklist { add, remove } => {
+            execute_update_blocklist(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateAllowlist { add, remove } => {
+            execute_update_allowlist(deps, env, info, add, remove)
+        }
+        ExecuteMsg::UpdateFee { fee } => execute_update_fee(deps, env, info, fee),
+        ExecuteMsg::AddMinter { minter, cap, quota } => {
+            execute_add_minter(deps, env, info, minter, cap, quota)
+        }
+        ExecuteMsg::RemoveMinter { minter } => execute_remove_minter(deps, env, info, minter),
+        ExecuteMsg::UpdateMinterQuota { minter, cap, quota } => {
+            execute_update_minter_quota(deps, env, info, minter, cap, quota)
+        }
+        ExecuteMsg::UpdateTokenInfo { name, symbol } => {
+            execute_update_token_info(deps, env, info, name, symbol)
+        }
+        ExecuteMsg::UpdateCap { cap } => execute_update_cap(deps, env, info, cap),
+        ExecuteMsg::ForceTransfer { from, to, amount } => {
+            execute_force_transfer(deps, env, info, from, to, amount)
+        }
+        ExecuteMsg::ForceBurn { from, amount } => execute_force_burn(deps, env, info, from, amount),
+        ExecuteMsg::RenounceClawbackAdmin {} => execute_renounce_clawback_admin(deps, env, info),
+    }
+}
+
+/// Returns `ContractError::Paused` if the pauser has frozen the token.
+/// Every handler moving, minting or burning tokens must call this first.
+pub fn ensure_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    match PAUSE_INFO.may_load(storage)? {
+        Some(PauseInfo { paused: true, .. }) => Err(ContractError::Paused {}),
+        _ => Ok(()),
     }
 }
 
 pub fn execute_transfer(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
+    ensure_valid_memo(deps.storage, memo.as_deref())?;
 
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    ensure_compliant(deps.storage, Some(&info.sender), &rcpt_addr)?;
 
-    BALANCES.update(
+    ensure_spendable(deps.storage, &env.block, &info.sender, amount)?;
+    balances().update(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let fee = charge_transfer_fee(
+        deps.storage,
+        env.block.height,
+        &info.sender,
+        &rcpt_addr,
+        amount,
+    )?;
+    let net_amount = amount - fee;
+    balances().update(
         deps.storage,
         &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + net_amount) },
     )?;
 
+    // fee and memo attributes are only added when set, so plain transfers emit the same
+    // attributes as before
     let res = Response::new()
         .add_attribute("action", "transfer")
-        .add_attribute("from", info.sender)
+        .add_attribute("from", &info.sender)
         .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
+        .add_attribute("amount", amount)
+        .add_attributes(fee_attributes(amount, fee))
+        .add_attributes(memo_attributes(memo.as_deref()))
+        .add_event(
+            TransferEvent {
+                from: info.sender.into(),
+                to: rcpt_addr.into(),
+                amount,
+                by: None,
+                memo,
+            }
+            .into_event()
+            .add_attributes(fee_attributes(amount, fee)),
+        );
     Ok(res)
 }
 
 pub fn execute_burn(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
+    ensure_valid_memo(deps.storage, memo.as_deref())?;
+
+    ensure_spendable(deps.storage, &env.block, &info.sender, amount)?;
 
     // lower balance
-    BALANCES.update(
+    balances().update(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
-        info.total_supply = info.total_supply.checked_sub(amount)?;
-        Ok(info)
-    })?;
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    config.total_supply = config
+        .total_supply
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
 
     let res = Response::new()
         .add_attribute("action", "burn")
-        .add_attribute("from", info.sender)
-        .add_attribute("amount", amount);
+        .add_attribute("from", &info.sender)
+        .add_attribute("amount", amount)
+        .add_attributes(memo_attributes(memo.as_deref()))
+        .add_event(
+            BurnEvent {
+                from: info.sender.into(),
+                amount,
+                by: None,
+                memo,
+            }
+            .into_event(),
+        );
     Ok(res)
 }
 
 pub fn execute_mint(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
@@ -311,71 +477,188 @@ pub fn execute_mint(
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    mint_tokens(deps, &env, &info.sender, &[(rcpt_addr, amount)], amount)?;
+
+    let res = Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("to", &recipient)
+        .add_attribute("amount", amount)
+        .add_event(
+            MintEvent {
+                to: recipient,
+                amount,
+            }
+            .into_event(),
+        );
+    Ok(res)
+}
+
+/// Mints straight into a contract and notifies it with a `Cw20ReceiveMsg`, as if the minter
+/// had sent freshly minted tokens. The minter is the `sender` of the message.
+pub fn execute_mint_and_send(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    if amount == Uint128::zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    mint_tokens(deps, &env, &info.sender, &[(rcpt_addr, amount)], amount)?;
+
+    let res = Response::new()
+        .add_attribute("action", "mint_and_send")
+        .add_attribute("to", &contract)
+        .add_attribute("amount", amount)
+        .add_event(
+            MintEvent {
+                to: contract.clone(),
+                amount,
+            }
+            .into_event(),
+        )
+        .add_message(
+            Cw20ReceiveMsg {
+                sender: info.sender.into(),
+                amount,
+                msg,
+                memo: None,
+            }
+            .into_cosmos_msg(contract)?,
+        );
+    Ok(res)
+}
+
+/// Mints for several accounts at once. Cap and minter limits are checked against the total of
+/// the whole batch, and the token info is saved only once.
+pub fn execute_mint_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mints: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    let (recipients, total) = validate_batch(
+        deps.api,
+        mints.iter().map(|m| (m.address.as_str(), m.amount)),
+    )?;
+    let mints = recipients
+        .into_iter()
+        .zip(mints.into_iter().map(|m| m.amount))
+        .collect::<Vec<_>>();
+    mint_tokens(deps, &env, &info.sender, &mints, total)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "mint_batch")
+        .add_attribute("amount", total);
+    for (rcpt_addr, amount) in mints {
+        res = res.add_event(
+            MintEvent {
+                to: rcpt_addr.into(),
+                amount,
+            }
+            .into_event(),
+        );
+    }
+    Ok(res)
+}
+
+/// Checks that `minter` may mint `total` more tokens and credits each recipient its amount.
+/// `total` must be the sum of all amounts.
+fn mint_tokens(
+    deps: DepsMut,
+    env: &Env,
+    minter: &Addr,
+    mints: &[(Addr, Uint128)],
+    total: Uint128,
+) -> Result<(), ContractError> {
+    ensure_not_paused(deps.storage)?;
 
     let mut config = TOKEN_INFO
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
 
-    if config
-        .mint
-        .as_ref()
-        .ok_or(ContractError::Unauthorized {})?
-        .minter
-        != info.sender
-    {
-        return Err(ContractError::Unauthorized {});
+    // besides the primary minter, registered minters may mint within their own limits
+    let is_primary_minter = matches!(&config.mint, Some(mint) if mint.minter == *minter);
+    if !is_primary_minter {
+        ensure_registered_minter(deps.storage, minter)?;
     }
 
     // update supply and enforce cap
-    config.total_supply += amount;
+    config.total_supply = config
+        .total_supply
+        .checked_add(total)
+        .map_err(StdError::overflow)?;
     if let Some(limit) = config.get_cap() {
         if config.total_supply > limit {
             return Err(ContractError::CannotExceedCap {});
         }
     }
-    TOKEN_INFO.save(deps.storage, &config)?;
-
-    // add amount to recipient balance
-    let rcpt_addr = deps.api.addr_validate(&recipient)?;
-    BALANCES.update(
-        deps.storage,
-        &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
-    )?;
+    for (recipient, _) in mints {
+        ensure_compliant(deps.storage, None, recipient)?;
+    }
+    if !is_primary_minter {
+        consume_mint_allowance(deps.storage, &env.block, minter, total)?;
+    }
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
 
-    let res = Response::new()
-        .add_attribute("action", "mint")
-        .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
-    Ok(res)
+    // add amounts to recipient balances
+    for (recipient, amount) in mints {
+        balances().update(
+            deps.storage,
+            recipient,
+            env.block.height,
+            |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        )?;
+    }
+    Ok(())
 }
 
 pub fn execute_send(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     contract: String,
     amount: Uint128,
     msg: Binary,
+    memo: Option<String>,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
+    ensure_valid_memo(deps.storage, memo.as_deref())?;
 
     let rcpt_addr = deps.api.addr_validate(&contract)?;
+    ensure_compliant(deps.storage, Some(&info.sender), &rcpt_addr)?;
+
+    ensure_spendable(deps.storage, &env.block, &info.sender, amount)?;
 
     // move the tokens to the contract
-    BALANCES.update(
+    balances().update(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let fee = charge_transfer_fee(
         deps.storage,
+        env.block.height,
+        &info.sender,
         &rcpt_addr,
-        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+        amount,
+    )?;
+    let net_amount = amount - fee;
+    balances().update(
+        deps.storage,
+        &rcpt_addr,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + net_amount) },
     )?;
 
     let res = Response::new()
@@ -383,20 +666,197 @@ pub fn execute_send(
         .add_attribute("from", &info.sender)
         .add_attribute("to", &contract)
         .add_attribute("amount", amount)
+        .add_attributes(fee_attributes(amount, fee))
+        .add_attributes(memo_attributes(memo.as_deref()))
+        .add_event(
+            SendEvent {
+                from: info.sender.to_string(),
+                to: rcpt_addr.into(),
+                amount,
+                by: None,
+                memo: memo.clone(),
+            }
+            .into_event()
+            .add_attributes(fee_attributes(amount, fee)),
+        )
         .add_message(
             Cw20ReceiveMsg {
                 sender: info.sender.into(),
-                amount,
+                amount: net_amount,
                 msg,
+                memo,
             }
             .into_cosmos_msg(contract)?,
         );
     Ok(res)
 }
 
+/// Validates the recipients of a batch, rejecting empty batches, zero amounts and duplicate
+/// addresses. Returns the validated addresses (in the original order) and the total amount.
+pub fn validate_batch<'a>(
+    api: &dyn Api,
+    entries: impl IntoIterator<Item = (&'a str, Uint128)>,
+) -> Result<(Vec<Addr>, Uint128), ContractError> {
+    let mut recipients = vec![];
+    let mut total = Uint128::zero();
+    for (address, amount) in entries {
+        if amount == Uint128::zero() {
+            return Err(ContractError::InvalidZeroAmount {});
+        }
+        recipients.push(api.addr_validate(address)?);
+        total = total.checked_add(amount).map_err(StdError::overflow)?;
+    }
+    if recipients.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+
+    let mut unique = recipients.iter().collect::<Vec<_>>();
+    unique.sort();
+    unique.dedup();
+    if unique.len() != recipients.len() {
+        return Err(ContractError::DuplicateBatchAddresses {});
+    }
+
+    Ok((recipients, total))
+}
+
+pub fn execute_transfer_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfers: Vec<Cw20Coin>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
+    let (recipients, total) = validate_batch(
+        deps.api,
+        transfers.iter().map(|t| (t.address.as_str(), t.amount)),
+    )?;
+
+    ensure_spendable(deps.storage, &env.block, &info.sender, total)?;
+
+    // debit the sender once for the whole batch
+    balances().update(
+        deps.storage,
+        &info.sender,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(total)?)
+        },
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "transfer_batch")
+        .add_attribute("from", &info.sender)
+        .add_attribute("amount", total);
+    for (rcpt_addr, transfer) in recipients.into_iter().zip(transfers) {
+        ensure_compliant(deps.storage, Some(&info.sender), &rcpt_addr)?;
+        let amount = transfer.amount;
+        let fee = charge_transfer_fee(
+            deps.storage,
+            env.block.height,
+            &info.sender,
+            &rcpt_addr,
+            amount,
+        )?;
+        let net_amount = amount - fee;
+        balances().update(
+            deps.storage,
+            &rcpt_addr,
+            env.block.height,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default() + net_amount)
+            },
+        )?;
+        res = res.add_event(
+            TransferEvent {
+                from: info.sender.to_string(),
+                to: rcpt_addr.into(),
+                amount,
+                by: None,
+                memo: None,
+            }
+            .into_event()
+            .add_attributes(fee_attributes(amount, fee)),
+        );
+    }
+    Ok(res)
+}
+
+pub fn execute_send_batch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sends: Vec<Cw20Send>,
+) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage)?;
+    let (recipients, total) = validate_batch(
+        deps.api,
+        sends.iter().map(|s| (s.contract.as_str(), s.amount)),
+    )?;
+
+    ensure_spendable(deps.storage, &env.block, &info.sender, total)?;
+
+    // debit the sender once for the whole batch
+    balances().update(
+        deps.storage,
+        &info.sender,
+        env.block.height,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(total)?)
+        },
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "send_batch")
+        .add_attribute("from", &info.sender)
+        .add_attribute("amount", total);
+    for (rcpt_addr, send) in recipients.into_iter().zip(sends) {
+        ensure_compliant(deps.storage, Some(&info.sender), &rcpt_addr)?;
+        let amount = send.amount;
+        let fee = charge_transfer_fee(
+            deps.storage,
+            env.block.height,
+            &info.sender,
+            &rcpt_addr,
+            amount,
+        )?;
+        let net_amount = amount - fee;
+        balances().update(
+            deps.storage,
+            &rcpt_addr,
+            env.block.height,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default() + net_amount)
+            },
+        )?;
+        res = res
+            .add_event(
+                SendEvent {
+                    from: info.sender.to_string(),
+                    to: rcpt_addr.to_string(),
+                    amount,
+                    by: None,
+                    memo: None,
+                }
+                .into_event()
+                .add_attributes(fee_attributes(amount, fee)),
+            )
+            .add_message(
+                Cw20ReceiveMsg {
+                    sender: info.sender.to_string(),
+                    amount: net_amount,
+                    msg: send.msg,
+                    memo: None,
+                }
+                .into_cosmos_msg(rcpt_addr)?,
+            );
+    }
+    Ok(res)
+}
+
 pub fn execute_update_minter(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     new_minter: Option<String>,
 ) -> Result<Response, ContractError> {
@@ -408,6 +868,16 @@ pub fn execute_update_minter(
     if mint.minter != info.sender {
         return Err(ContractError::Unauthorized {});
     }
+    // registered minters rely on the cap of the primary minter to bound the supply
+    if new_minter.is_none()
+        && mint.cap.is_some()
+        && MINTERS
+            .keys(deps.storage, None, None, Ascending)
+            .next()
+            .is_some()
+    {
+        return Err(ContractError::MintersRegistered {});
+    }
 
     let minter_data = new_minter
         .map(|new_minter| deps.api.addr_validate(&new_minter))
@@ -419,7 +889,7 @@ pub fn execute_update_minter(
 
     config.mint = minter_data;
 
-    TOKEN_INFO.save(deps.storage, &config)?;
+    TOKEN_INFO.save(deps.storage, &config, env.block.height)?;
 
     Ok(Response::default()
         .add_attribute("action", "update_minter")
@@ -432,6 +902,26 @@ pub fn execute_update_minter(
         ))
 }
 
+pub fn execute_set_paused(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut pause_info = PAUSE_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if pause_info.pauser != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    pause_info.paused = paused;
+    PAUSE_INFO.save(deps.storage, &pause_info)?;
+
+    let action = if paused { "pause" } else { "unpause" };
+    Ok(Response::new().add_attribute("action", action))
+}
+
 pub fn execute_update_marketing(
     deps: DepsMut,
     _env: Env,
@@ -495,7 +985,7 @@ pub fn execute_upload_logo(
         .may_load(deps.storage)?
         .ok_or(ContractError::Unauthorized {})?;
 
-    verify_logo(&logo)?;
+    logo.validate()?;
 
     if marketing_info
         .marketing
@@ -521,43 +1011,92 @@ pub fn execute_upload_logo(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, env, address)?),
+        QueryMsg::BalanceAt { address, height } => {
+            to_binary(&query_balance_at(deps, address, height)?)
+        }
         QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::TotalSupplyAt { height } => to_binary(&query_total_supply_at(deps, height)?),
         QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
         QueryMsg::Allowance { owner, spender } => {
-            to_binary(&query_allowance(deps, owner, spender)?)
+            to_binary(&query_allowance(deps, env, owner, spender)?)
         }
         QueryMsg::AllAllowances {
             owner,
             start_after,
             limit,
-        } => to_binary(&query_owner_allowances(deps, owner, start_after, limit)?),
+            include_expired,
+        } => to_binary(&query_owner_allowances(
+            deps,
+            env,
+            owner,
+            start_after,
+            limit,
+            include_expired,
+        )?),
         QueryMsg::AllSpenderAllowances {
             spender,
             start_after,
             limit,
+            include_expired,
         } => to_binary(&query_spender_allowances(
             deps,
+            env,
             spender,
             start_after,
             limit,
+            include_expired,
         )?),
         QueryMsg::AllAccounts { start_after, limit } => {
             to_binary(&query_all_accounts(deps, start_after, limit)?)
         }
+        QueryMsg::AllBalances { start_after, limit } => {
+            to_binary(&query_all_balances(deps, start_after, limit)?)
+        }
+        QueryMsg::TopHolders { limit, start_after } => {
+            to_binary(&query_top_holders(deps, limit, start_after)?)
+        }
         QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
         QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        QueryMsg::PauseInfo {} => to_binary(&query_pause_info(deps)?),
+        QueryMsg::ComplianceInfo {} => to_binary(&query_compliance_info(deps)?),
+        QueryMsg::Blocklist { start_after, limit } => {
+            to_binary(&query_blocklist(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowlist { start_after, limit } => {
+            to_binary(&query_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::FeeConfig {} => to_binary(&query_fee_config(deps)?),
+        QueryMsg::AllMinters { start_after, limit } => {
+            to_binary(&query_all_minters(deps, env, start_after, limit)?)
+        }
+        QueryMsg::VestingInfo { address } => to_binary(&query_vesting_info(deps, env, address)?),
+        QueryMsg::TokenAdmin {} => to_binary(&query_token_admin(deps)?),
+        QueryMsg::ClawbackAdmin {} => to_binary(&query_clawback_admin(deps)?),
+        QueryMsg::PermitNonce { owner } => to_binary(&query_permit_nonce(deps, owner)?),
     }
 }
 
-pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+pub fn query_balance(deps: Deps, env: Env, address: String) -> StdResult<BalanceResponse> {
     let address = deps.api.addr_validate(&address)?;
-    let balance = BALANCES
+    let balance = balances()
         .may_load(deps.storage, &address)?
         .unwrap_or_default();
-    Ok(BalanceResponse { balance })
+    let spendable = spendable_balance(deps.storage, &address, balance, env.block.time)?;
+    Ok(BalanceResponse { balance, spendable })
+}
+
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = balances()
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    Ok(BalanceResponse {
+        balance,
+        spendable: None,
+    })
 }
 
 pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
@@ -571,6 +1110,14 @@ pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
     Ok(res)
 }
 
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    let total_supply = TOKEN_INFO
+        .may_load_at_height(deps.storage, height)?
+        .map(|info| info.total_supply)
+        .unwrap_or_default();
+    Ok(TotalSupplyResponse { total_supply })
+}
+
 pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
     let meta = TOKEN_INFO.load(deps.storage)?;
     let minter = match meta.mint {
@@ -583,6 +1130,20 @@ pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
     Ok(minter)
 }
 
+pub fn query_pause_info(deps: Deps) -> StdResult<PauseInfoResponse> {
+    let res = match PAUSE_INFO.may_load(deps.storage)? {
+        Some(info) => PauseInfoResponse {
+            pauser: Some(info.pauser.into()),
+            paused: info.paused,
+        },
+        None => PauseInfoResponse {
+            pauser: None,
+            paused: false,
+        },
+    };
+    Ok(res)
+}
+
 pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
     Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
 }
@@ -590,20 +1151,16 @@ pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
 pub fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
     let logo = LOGO.load(deps.storage)?;
     match logo {
-        Logo::Embedded(EmbeddedLogo::Svg(logo)) => Ok(DownloadLogoResponse {
-            mime_type: "image/svg+xml".to_owned(),
-            data: logo,
-        }),
-        Logo::Embedded(EmbeddedLogo::Png(logo)) => Ok(DownloadLogoResponse {
-            mime_type: "image/png".to_owned(),
-            data: logo,
+        Logo::Embedded(logo) => Ok(DownloadLogoResponse {
+            mime_type: logo.mime_type().to_owned(),
+            data: logo.data().clone(),
         }),
         Logo::Url(_) => Err(StdError::not_found("logo")),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let original_version =
         ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -616,6 +1173,32 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
             ALLOWANCES_SPENDER.save(deps.storage, (&spender, &owner), &allowance)?;
         }
     }
+
+    // Contracts instantiated before snapshots were introduced have no changelog at all.
+    // Re-save the current state at the migration height, so that it is recorded as the
+    // starting point of the history.
+    let height = env.block.height;
+    let history_missing = TOKEN_INFO.changelog().is_empty(deps.storage);
+    if history_missing {
+        let info = TOKEN_INFO.load(deps.storage)?;
+        TOKEN_INFO.save(deps.storage, &info, height)?;
+    }
+    // The balance index was introduced later still, and may need to be built as well.
+    let index_missing = balances()
+        .idx
+        .amount
+        .keys_raw(deps.storage, None, None, Ascending)
+        .next()
+        .is_none();
+    if history_missing || index_missing {
+        let accounts = balances()
+            .range(deps.storage, None, None, Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (addr, balance) in accounts {
+            balances().replace(deps.storage, &addr, Some(&balance), None, height)?;
+        }
+    }
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     Ok(Response::default())
 }
 
@@ -624,13 +1207,18 @@ mod tests {
     use cosmwasm_std::testing::{
         mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
     };
-    use cosmwasm_std::{coins, from_binary, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
+    use cosmwasm_std::{
+        attr, coins, from_binary, Addr, CosmosMsg, Event, StdError, SubMsg, WasmMsg,
+    };
+    use cw20::{EmbeddedLogo, LogoError};
 
     use super::*;
     use crate::msg::InstantiateMarketingInfo;
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
-        query_balance(deps, address.into()).unwrap().balance
+        query_balance(deps, mock_env(), address.into())
+            .unwrap()
+            .balance
     }
 
     // this will set up the instantiation for other tests
@@ -674,6 +1262,7 @@ mod tests {
             }],
             mint: mint.clone(),
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -695,7 +1284,11 @@ mod tests {
         meta
     }
 
-    const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    // the header and IHDR chunk of a 1x1 PNG
+    const PNG_HEADER: [u8; 33] = [
+        0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0,
+        0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0, 0x1f, 0x15, 0xc4, 0x89,
+    ];
 
     mod instantiate {
         use super::*;
@@ -714,6 +1307,7 @@ mod tests {
                 }],
                 mint: None,
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -754,6 +1348,7 @@ mod tests {
                     cap: Some(limit),
                 }),
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -801,6 +1396,7 @@ mod tests {
                     cap: Some(limit),
                 }),
                 marketing: None,
+                ..InstantiateMsg::default()
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -827,8 +1423,9 @@ mod tests {
                         project: Some("Project".to_owned()),
                         description: Some("Description".to_owned()),
                         marketing: Some("marketing".to_owned()),
-                        logo: Some(Logo::Url("url".to_owned())),
+                        logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                     }),
+                    ..InstantiateMsg::default()
                 };
 
                 let info = mock_info("creator", &[]);
@@ -842,7 +1439,7 @@ mod tests {
                         project: Some("Project".to_owned()),
                         description: Some("Description".to_owned()),
                         marketing: Some(Addr::unchecked("marketing")),
-                        logo: Some(LogoInfo::Url("url".to_owned())),
+                        logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                     }
                 );
 
@@ -867,8 +1464,9 @@ mod tests {
                         project: Some("Project".to_owned()),
                         description: Some("Description".to_owned()),
                         marketing: Some("m".to_owned()),
-                        logo: Some(Logo::Url("url".to_owned())),
+                        logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                     }),
+                    ..InstantiateMsg::default()
                 };
 
                 let info = mock_info("creator", &[]);
@@ -953,6 +1551,144 @@ mod tests {
         assert_eq!(err, ContractError::Unauthorized {});
     }
 
+    #[test]
+    fn mint_and_send() {
+        let mut deps = mock_dependencies();
+        let genesis = String::from("genesis");
+        let minter = String::from("minter");
+        let vault = String::from("vault");
+        let amount = Uint128::new(1000);
+        let send_msg = Binary::from(r#"{"stake":{}}"#.as_bytes());
+        do_instantiate_with_minter(
+            deps.as_mut(),
+            &genesis,
+            amount,
+            &minter,
+            Some(Uint128::new(1500)),
+        );
+
+        // same rules as for minting
+        let msg = ExecuteMsg::MintAndSend {
+            contract: vault.clone(),
+            amount: Uint128::new(200),
+            msg: send_msg.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&genesis, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("action", "mint_and_send"),
+                attr("to", &vault),
+                attr("amount", "200"),
+            ]
+        );
+
+        // the vault is told the minter sent the new tokens
+        let binary_msg = Cw20ReceiveMsg {
+            sender: minter.clone(),
+            amount: Uint128::new(200),
+            msg: send_msg.clone(),
+            memo: None,
+        }
+        .into_binary()
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: vault.clone(),
+                msg: binary_msg,
+                funds: vec![],
+            }))]
+        );
+        assert_eq!(get_balance(deps.as_ref(), &genesis), amount);
+        assert_eq!(get_balance(deps.as_ref(), &vault), Uint128::new(200));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(1200)
+        );
+
+        let msg = ExecuteMsg::MintAndSend {
+            contract: vault,
+            amount: Uint128::new(301),
+            msg: send_msg,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+    }
+
+    #[test]
+    fn mint_batch() {
+        let mut deps = mock_dependencies();
+        let minter = String::from("minter");
+        do_instantiate_with_minter(
+            deps.as_mut(),
+            "genesis",
+            Uint128::new(1000),
+            &minter,
+            Some(Uint128::new(2000)),
+        );
+        let coin = |address: &str, amount| Cw20Coin {
+            address: address.into(),
+            amount: Uint128::new(amount),
+        };
+
+        let msg = ExecuteMsg::MintBatch {
+            mints: vec![coin("alice", 300), coin("bob", 200)],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("genesis", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![attr("action", "mint_batch"), attr("amount", "500")]
+        );
+        assert_eq!(
+            res.events,
+            vec![
+                Event::new("mint")
+                    .add_attribute("to", "alice")
+                    .add_attribute("amount", "300"),
+                Event::new("mint")
+                    .add_attribute("to", "bob")
+                    .add_attribute("amount", "200"),
+            ]
+        );
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::new(300));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::new(200));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(1500)
+        );
+
+        // the cap applies to the whole batch
+        let msg = ExecuteMsg::MintBatch {
+            mints: vec![coin("alice", 300), coin("carol", 201)],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::new(300));
+
+        let msg = ExecuteMsg::MintBatch {
+            mints: vec![coin("alice", 1), coin("bob", 0)],
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidZeroAmount {});
+    }
+
     #[test]
     fn minter_can_update_minter_but_not_cap() {
         let mut deps = mock_dependencies();
@@ -1085,6 +1821,7 @@ mod tests {
             ],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let err =
             instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap_err();
@@ -1107,6 +1844,7 @@ mod tests {
             ],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let res = instantiate(deps.as_mut(), env, info, instantiate_msg).unwrap();
         assert_eq!(0, res.messages.len());
@@ -1177,6 +1915,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: Uint128::zero(),
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidZeroAmount {});
@@ -1187,6 +1926,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: too_much,
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -1197,6 +1937,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr1.clone(),
             amount: transfer,
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -1207,6 +1948,7 @@ mod tests {
         let msg = ExecuteMsg::Transfer {
             recipient: addr2.clone(),
             amount: transfer,
+            memo: None,
         };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 0);
@@ -1235,6 +1977,7 @@ mod tests {
         let env = mock_env();
         let msg = ExecuteMsg::Burn {
             amount: Uint128::zero(),
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidZeroAmount {});
@@ -1246,7 +1989,10 @@ mod tests {
         // cannot burn more than we have
         let info = mock_info(addr1.as_ref(), &[]);
         let env = mock_env();
-        let msg = ExecuteMsg::Burn { amount: too_much };
+        let msg = ExecuteMsg::Burn {
+            amount: too_much,
+            memo: None,
+        };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
         assert_eq!(
@@ -1257,7 +2003,10 @@ mod tests {
         // valid burn reduces total supply
         let info = mock_info(addr1.as_ref(), &[]);
         let env = mock_env();
-        let msg = ExecuteMsg::Burn { amount: burn };
+        let msg = ExecuteMsg::Burn {
+            amount: burn,
+            memo: None,
+        };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 0);
 
@@ -1288,6 +2037,7 @@ mod tests {
             contract: contract.clone(),
             amount: Uint128::zero(),
             msg: send_msg.clone(),
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert_eq!(err, ContractError::InvalidZeroAmount {});
@@ -1299,6 +2049,7 @@ mod tests {
             contract: contract.clone(),
             amount: too_much,
             msg: send_msg.clone(),
+            memo: None,
         };
         let err = execute(deps.as_mut(), env, info, msg).unwrap_err();
         assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
@@ -1310,6 +2061,7 @@ mod tests {
             contract: contract.clone(),
             amount: transfer,
             msg: send_msg.clone(),
+            memo: None,
         };
         let res = execute(deps.as_mut(), env, info, msg).unwrap();
         assert_eq!(res.messages.len(), 1);
@@ -1320,6 +2072,7 @@ mod tests {
             sender: addr1.clone(),
             amount: transfer,
             msg: send_msg,
+            memo: None,
         }
         .into_binary()
         .unwrap();
@@ -1343,6 +2096,437 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transfer_batch() {
+        let mut deps = mock_dependencies();
+        let addr1 = String::from("addr0001");
+        let addr2 = String::from("addr0002");
+        let addr3 = String::from("addr0003");
+        let amount1 = Uint128::new(12340000);
+        let transfer2 = Uint128::new(76543);
+        let transfer3 = Uint128::new(1000);
+
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+        let info = mock_info(addr1.as_ref(), &[]);
+
+        // cannot transfer an empty batch
+        let msg = ExecuteMsg::TransferBatch { transfers: vec![] };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::EmptyBatch {});
+
+        // cannot transfer nothing to anyone
+        let msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: transfer2,
+                },
+                Cw20Coin {
+                    address: addr3.clone(),
+                    amount: Uint128::zero(),
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidZeroAmount {});
+
+        // cannot list the same recipient twice
+        let msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: transfer2,
+                },
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: transfer3,
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateBatchAddresses {});
+
+        // cannot send more than we have in total
+        let msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: amount1,
+                },
+                Cw20Coin {
+                    address: addr3.clone(),
+                    amount: transfer3,
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+        assert_eq!(get_balance(deps.as_ref(), addr1.clone()), amount1);
+        assert_eq!(get_balance(deps.as_ref(), addr2.clone()), Uint128::zero());
+
+        // valid batch
+        let msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                Cw20Coin {
+                    address: addr2.clone(),
+                    amount: transfer2,
+                },
+                Cw20Coin {
+                    address: addr3.clone(),
+                    amount: transfer3,
+                },
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+        assert_eq!(
+            res.events,
+            vec![
+                Event::new("transfer")
+                    .add_attribute("from", &addr1)
+                    .add_attribute("to", &addr2)
+                    .add_attribute("amount", transfer2),
+                Event::new("transfer")
+                    .add_attribute("from", &addr1)
+                    .add_attribute("to", &addr3)
+                    .add_attribute("amount", transfer3),
+            ]
+        );
+
+        let remainder = amount1 - transfer2 - transfer3;
+        assert_eq!(get_balance(deps.as_ref(), addr1), remainder);
+        assert_eq!(get_balance(deps.as_ref(), addr2), transfer2);
+        assert_eq!(get_balance(deps.as_ref(), addr3), transfer3);
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            amount1
+        );
+    }
+
+    #[test]
+    fn send_batch() {
+        let mut deps = mock_dependencies();
+        let addr1 = String::from("addr0001");
+        let contract1 = String::from("contract0001");
+        let contract2 = String::from("contract0002");
+        let amount1 = Uint128::new(12340000);
+        let send1 = Uint128::new(76543);
+        let send2 = Uint128::new(1000);
+        let msg1 = Binary::from(r#"{"some":123}"#.as_bytes());
+        let msg2 = Binary::from(r#"{"other":456}"#.as_bytes());
+
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+        let info = mock_info(addr1.as_ref(), &[]);
+
+        // cannot list the same contract twice
+        let msg = ExecuteMsg::SendBatch {
+            sends: vec![
+                Cw20Send {
+                    contract: contract1.clone(),
+                    amount: send1,
+                    msg: msg1.clone(),
+                },
+                Cw20Send {
+                    contract: contract1.clone(),
+                    amount: send2,
+                    msg: msg2.clone(),
+                },
+            ],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateBatchAddresses {});
+
+        // valid batch notifies every contract with its own message
+        let msg = ExecuteMsg::SendBatch {
+            sends: vec![
+                Cw20Send {
+                    contract: contract1.clone(),
+                    amount: send1,
+                    msg: msg1.clone(),
+                },
+                Cw20Send {
+                    contract: contract2.clone(),
+                    amount: send2,
+                    msg: msg2.clone(),
+                },
+            ],
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(
+                    Cw20ReceiveMsg {
+                        sender: addr1.clone(),
+                        amount: send1,
+                        msg: msg1,
+                        memo: None,
+                    }
+                    .into_cosmos_msg(&contract1)
+                    .unwrap()
+                ),
+                SubMsg::new(
+                    Cw20ReceiveMsg {
+                        sender: addr1.clone(),
+                        amount: send2,
+                        msg: msg2,
+                        memo: None,
+                    }
+                    .into_cosmos_msg(&contract2)
+                    .unwrap()
+                ),
+            ]
+        );
+        assert_eq!(res.events.len(), 2);
+
+        assert_eq!(get_balance(deps.as_ref(), addr1), amount1 - send1 - send2);
+        assert_eq!(get_balance(deps.as_ref(), contract1), send1);
+        assert_eq!(get_balance(deps.as_ref(), contract2), send2);
+    }
+
+    #[test]
+    fn balance_and_supply_snapshots() {
+        let mut deps = mock_dependencies();
+        let addr1 = String::from("addr0001");
+        let addr2 = String::from("addr0002");
+        let minter = String::from("minter");
+        let amount1 = Uint128::new(12340000);
+        let transfer = Uint128::new(76543);
+        let prize = Uint128::new(1000);
+
+        let mut env = mock_env();
+        let start = env.block.height;
+        do_instantiate_with_minter(deps.as_mut(), &addr1, amount1, &minter, None);
+
+        // transfer in a later block
+        env.block.height = start + 10;
+        let msg = ExecuteMsg::Transfer {
+            recipient: addr2.clone(),
+            amount: transfer,
+            memo: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), msg).unwrap();
+
+        // mint even later
+        env.block.height = start + 20;
+        let msg = ExecuteMsg::Mint {
+            recipient: addr2.clone(),
+            amount: prize,
+        };
+        execute(deps.as_mut(), env, mock_info(&minter, &[]), msg).unwrap();
+
+        let balance_at = |addr: &str, height: u64| {
+            query_balance_at(deps.as_ref(), addr.to_string(), height)
+                .unwrap()
+                .balance
+        };
+        let supply_at = |height: u64| {
+            query_total_supply_at(deps.as_ref(), height)
+                .unwrap()
+                .total_supply
+        };
+
+        // snapshots reflect the state at the beginning of the given block
+        assert_eq!(balance_at(&addr1, start), Uint128::zero());
+        assert_eq!(balance_at(&addr1, start + 1), amount1);
+        assert_eq!(balance_at(&addr1, start + 10), amount1);
+        assert_eq!(balance_at(&addr1, start + 11), amount1 - transfer);
+        assert_eq!(balance_at(&addr2, start + 10), Uint128::zero());
+        assert_eq!(balance_at(&addr2, start + 11), transfer);
+        assert_eq!(balance_at(&addr2, start + 20), transfer);
+        assert_eq!(balance_at(&addr2, start + 21), transfer + prize);
+
+        assert_eq!(supply_at(start), Uint128::zero());
+        assert_eq!(supply_at(start + 1), amount1);
+        assert_eq!(supply_at(start + 20), amount1);
+        assert_eq!(supply_at(start + 21), amount1 + prize);
+
+        // and the query entry point serves them as well
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::BalanceAt {
+                address: addr2,
+                height: start + 11,
+            },
+        )
+        .unwrap();
+        let loaded: BalanceResponse = from_binary(&data).unwrap();
+        assert_eq!(loaded.balance, transfer);
+
+        let data = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TotalSupplyAt { height: start + 21 },
+        )
+        .unwrap();
+        let loaded: TotalSupplyResponse = from_binary(&data).unwrap();
+        assert_eq!(loaded.total_supply, amount1 + prize);
+    }
+
+    #[test]
+    fn pause_and_unpause() {
+        let mut deps = mock_dependencies();
+        let genesis = String::from("genesis");
+        let minter = String::from("minter");
+        let pauser = String::from("pauser");
+        let amount = Uint128::new(1234);
+
+        let instantiate_msg = InstantiateMsg {
+            name: "Cash Token".to_string(),
+            symbol: "CASH".to_string(),
+            decimals: 9,
+            initial_balances: vec![Cw20Coin {
+                address: genesis.clone(),
+                amount,
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            pauser: Some(pauser.clone()),
+            ..InstantiateMsg::default()
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            instantiate_msg,
+        )
+        .unwrap();
+        assert_eq!(
+            query_pause_info(deps.as_ref()).unwrap(),
+            PauseInfoResponse {
+                pauser: Some(pauser.clone()),
+                paused: false,
+            }
+        );
+
+        // only the pauser can pause
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&genesis, &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&pauser, &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap();
+        assert!(query_pause_info(deps.as_ref()).unwrap().paused);
+
+        // nothing moves while paused
+        let frozen = vec![
+            (
+                genesis.clone(),
+                ExecuteMsg::Transfer {
+                    recipient: "other".to_string(),
+                    amount: Uint128::new(1),
+                    memo: None,
+                },
+            ),
+            (
+                genesis.clone(),
+                ExecuteMsg::Send {
+                    contract: "contract".to_string(),
+                    amount: Uint128::new(1),
+                    msg: Binary::default(),
+                    memo: None,
+                },
+            ),
+            (
+                genesis.clone(),
+                ExecuteMsg::Burn {
+                    amount: Uint128::new(1),
+                    memo: None,
+                },
+            ),
+            (
+                "spender".to_string(),
+                ExecuteMsg::TransferFrom {
+                    owner: genesis.clone(),
+                    recipient: "other".to_string(),
+                    amount: Uint128::new(1),
+                    memo: None,
+                },
+            ),
+            (
+                "spender".to_string(),
+                ExecuteMsg::BurnFrom {
+                    owner: genesis.clone(),
+                    amount: Uint128::new(1),
+                },
+            ),
+            (
+                minter.clone(),
+                ExecuteMsg::Mint {
+                    recipient: "other".to_string(),
+                    amount: Uint128::new(1),
+                },
+            ),
+        ];
+        for (sender, msg) in frozen {
+            let err = execute(deps.as_mut(), mock_env(), mock_info(&sender, &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::Paused {});
+        }
+
+        // only the pauser can unpause, after which tokens move again
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&pauser, &[]),
+            ExecuteMsg::Unpause {},
+        )
+        .unwrap();
+        assert!(!query_pause_info(deps.as_ref()).unwrap().paused);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: "other".to_string(),
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(&genesis, &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "other"), Uint128::new(1));
+    }
+
+    #[test]
+    fn cannot_pause_without_pauser() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), "genesis", Uint128::new(1234));
+        assert_eq!(
+            query_pause_info(deps.as_ref()).unwrap(),
+            PauseInfoResponse {
+                pauser: None,
+                paused: false,
+            }
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("genesis", &[]),
+            ExecuteMsg::Pause {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
     mod migration {
         use super::*;
 
@@ -1380,6 +2564,7 @@ mod tests {
                         }],
                         mint: None,
                         marketing: None,
+                        ..InstantiateMsg::default()
                     },
                     &[],
                     "TOKEN",
@@ -1396,6 +2581,7 @@ mod tests {
                         owner: "sender".to_string(),
                         start_after: None,
                         limit: None,
+                        include_expired: None,
                     },
                 )
                 .unwrap();
@@ -1440,6 +2626,13 @@ mod tests {
 
             assert_eq!(balance.balance, Uint128::new(100));
 
+            // The supported interfaces are advertised
+            let token = cw20::Cw20Contract(cw20_addr.clone());
+            let supported = token.supported_interfaces(&app.wrap()).unwrap();
+            assert_eq!(supported, supported_interfaces());
+            assert!(token.has_allowance(&app.wrap()));
+            assert!(token.is_mintable(&app.wrap()));
+
             // Confirm that the allowance per spender is there
             let allowance: AllSpenderAllowancesResponse = app
                 .wrap()
@@ -1449,6 +2642,7 @@ mod tests {
                         spender: "spender".to_string(),
                         start_after: None,
                         limit: None,
+                        include_expired: None,
                     },
                 )
                 .unwrap();
@@ -1457,7 +2651,10 @@ mod tests {
                 &[SpenderAllowanceInfo {
                     owner: "sender".to_string(),
                     allowance: allow1,
-                    expires
+                    expires,
+                    recurring: None,
+                    next_reset: None,
+                    recipients: None,
                 }]
             );
         }
@@ -1479,8 +2676,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("marketing".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1508,7 +2706,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("marketing")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1533,8 +2731,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1561,7 +2760,7 @@ mod tests {
                     project: Some("New project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1586,8 +2785,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1614,7 +2814,7 @@ mod tests {
                     project: None,
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1639,8 +2839,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1667,7 +2868,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Better description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1692,8 +2893,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1720,7 +2922,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: None,
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1745,8 +2947,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1773,7 +2976,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("marketing")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1798,8 +3001,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1830,7 +3034,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1855,8 +3059,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1883,7 +3088,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: None,
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1908,8 +3113,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -1920,7 +3126,7 @@ mod tests {
                 deps.as_mut(),
                 mock_env(),
                 info,
-                ExecuteMsg::UploadLogo(Logo::Url("new_url".to_owned())),
+                ExecuteMsg::UploadLogo(Logo::Url("https://example.com/new_logo.png".to_owned())),
             )
             .unwrap();
 
@@ -1932,7 +3138,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("new_url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/new_logo.png".to_owned())),
                 }
             );
 
@@ -1957,8 +3163,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2007,8 +3214,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2058,8 +3266,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2075,7 +3284,7 @@ mod tests {
             )
             .unwrap_err();
 
-            assert_eq!(err, ContractError::LogoTooBig {});
+            assert_eq!(err, ContractError::Logo(LogoError::TooBig {}));
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -2083,7 +3292,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -2108,8 +3317,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2132,7 +3342,7 @@ mod tests {
             )
             .unwrap_err();
 
-            assert_eq!(err, ContractError::LogoTooBig {});
+            assert_eq!(err, ContractError::Logo(LogoError::TooBig {}));
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -2140,7 +3350,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -2165,8 +3375,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2182,7 +3393,7 @@ mod tests {
             )
             .unwrap_err();
 
-            assert_eq!(err, ContractError::InvalidPngHeader {});
+            assert_eq!(err, ContractError::Logo(LogoError::InvalidPngHeader {}));
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -2190,7 +3401,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -2215,8 +3426,9 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some("creator".to_owned()),
-                    logo: Some(Logo::Url("url".to_owned())),
+                    logo: Some(Logo::Url("https://example.com/logo.png".to_owned())),
                 }),
+                ..InstantiateMsg::default()
             };
 
             let info = mock_info("creator", &[]);
@@ -2233,7 +3445,7 @@ mod tests {
             )
             .unwrap_err();
 
-            assert_eq!(err, ContractError::InvalidXmlPreamble {});
+            assert_eq!(err, ContractError::Logo(LogoError::InvalidXmlPreamble {}));
 
             assert_eq!(
                 query_marketing_info(deps.as_ref()).unwrap(),
@@ -2241,7 +3453,7 @@ mod tests {
                     project: Some("Project".to_owned()),
                     description: Some("Description".to_owned()),
                     marketing: Some(Addr::unchecked("creator")),
-                    logo: Some(LogoInfo::Url("url".to_owned())),
+                    logo: Some(LogoInfo::Url("https://example.com/logo.png".to_owned())),
                 }
             );
 
@@ -1,10 +1,11 @@
-use cosmwasm_std::{Deps, Order, StdResult};
+use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
 use cw20::{
-    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
-    SpenderAllowanceInfo,
+    AllAccountsResponse, AllAllowancesResponse, AllBalancesResponse, AllSpenderAllowancesResponse,
+    AllowanceInfo, AllowanceResponse, Cw20Coin, SpenderAllowanceInfo, TopHoldersResponse,
 };
 
-use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES};
+use crate::allowances::refill_allowance;
+use crate::state::{balances, ALLOWANCES, ALLOWANCES_SPENDER};
 use cw_storage_plus::Bound;
 
 // settings for pagination
@@ -13,51 +14,85 @@ const DEFAULT_LIMIT: u32 = 10;
 
 pub fn query_owner_allowances(
     deps: Deps,
+    env: Env,
     owner: String,
     start_after: Option<String>,
     limit: Option<u32>,
+    include_expired: Option<bool>,
 ) -> StdResult<AllAllowancesResponse> {
     let owner_addr = deps.api.addr_validate(&owner)?;
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+    let include_expired = include_expired.unwrap_or_default();
 
-    let allowances = ALLOWANCES
+    let range = ALLOWANCES
         .prefix(&owner_addr)
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            item.map(|(addr, allow)| AllowanceInfo {
-                spender: addr.into(),
-                allowance: allow.allowance,
-                expires: allow.expires,
-            })
+        .range(deps.storage, start, None, Order::Ascending);
+    let allowances = load_allowances(range, &env, include_expired, limit)?
+        .into_iter()
+        .map(|(addr, allow)| AllowanceInfo {
+            spender: addr.into(),
+            allowance: allow.allowance,
+            expires: allow.expires,
+            recurring: allow.recurring,
+            next_reset: allow.next_reset,
+            recipients: allow.recipients,
         })
-        .collect::<StdResult<_>>()?;
+        .collect();
     Ok(AllAllowancesResponse { allowances })
 }
 
+/// Reads up to `limit` allowances, skipping expired ones unless `include_expired` is set.
+/// Recurring allowances are refilled as of the current block.
+fn load_allowances(
+    range: impl Iterator<Item = StdResult<(Addr, AllowanceResponse)>>,
+    env: &Env,
+    include_expired: bool,
+    limit: usize,
+) -> StdResult<Vec<(Addr, AllowanceResponse)>> {
+    let mut allowances = vec![];
+    for item in range {
+        if allowances.len() == limit {
+            break;
+        }
+        let (addr, mut allow) = item?;
+        if !include_expired && allow.expires.is_expired(&env.block) {
+            continue;
+        }
+        refill_allowance(&mut allow, &env.block);
+        allowances.push((addr, allow));
+    }
+    Ok(allowances)
+}
+
 pub fn query_spender_allowances(
     deps: Deps,
+    env: Env,
     spender: String,
     start_after: Option<String>,
     limit: Option<u32>,
+    include_expired: Option<bool>,
 ) -> StdResult<AllSpenderAllowancesResponse> {
     let spender_addr = deps.api.addr_validate(&spender)?;
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
-
-    let allowances = ALLOWANCES_SPENDER
-        .prefix(&spender_addr)
-        .range(deps.storage, start, None, Order::Ascending)
-        .take(limit)
-        .map(|item| {
-            item.map(|(addr, allow)| SpenderAllowanceInfo {
-                owner: addr.into(),
-                allowance: allow.allowance,
-                expires: allow.expires,
-            })
+    let include_expired = include_expired.unwrap_or_default();
+
+    let range =
+        ALLOWANCES_SPENDER
+            .prefix(&spender_addr)
+            .range(deps.storage, start, None, Order::Ascending);
+    let allowances = load_allowances(range, &env, include_expired, limit)?
+        .into_iter()
+        .map(|(addr, allow)| SpenderAllowanceInfo {
+            owner: addr.into(),
+            allowance: allow.allowance,
+            expires: allow.expires,
+            recurring: allow.recurring,
+            next_reset: allow.next_reset,
+            recipients: allow.recipients,
         })
-        .collect::<StdResult<_>>()?;
+        .collect();
     Ok(AllSpenderAllowancesResponse { allowances })
 }
 
@@ -69,7 +104,7 @@ pub fn query_all_accounts(
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
-    let accounts = BALANCES
+    let accounts = balances()
         .keys(deps.storage, start, None, Order::Ascending)
         .take(limit)
         .map(|item| item.map(Into::into))
@@ -78,16 +113,73 @@ pub fn query_all_accounts(
     Ok(AllAccountsResponse { accounts })
 }
 
+pub fn query_all_balances(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllBalancesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+    let balances = balances()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(addr, amount)| Cw20Coin {
+                address: addr.into(),
+                amount,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(AllBalancesResponse { balances })
+}
+
+pub fn query_top_holders(
+    deps: Deps,
+    limit: Option<u32>,
+    start_after: Option<Cw20Coin>,
+) -> StdResult<TopHoldersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    // index keys are (balance, address), so holders with equal balances are ordered by
+    // address and a page can end in the middle of them
+    let end = start_after
+        .map(|last| -> StdResult<_> {
+            let address = deps.api.addr_validate(&last.address)?;
+            Ok(Bound::exclusive((last.amount.u128(), address)))
+        })
+        .transpose()?;
+
+    // the typed `range` would try to parse the index entries as balances,
+    // so go through the raw variant which loads them from the primary map
+    let holders = balances()
+        .idx
+        .amount
+        .range_raw(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (addr, amount) = item?;
+            Ok(Cw20Coin {
+                address: String::from_utf8(addr)?,
+                amount,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(TopHoldersResponse { holders })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
     use cosmwasm_std::{coins, from_binary, DepsMut, Uint128};
-    use cw20::{Cw20Coin, Expiration, TokenInfoResponse};
+    use cw20::{Expiration, TokenInfoResponse};
+    use cw_storage_plus::Index;
 
-    use crate::contract::{execute, instantiate, query, query_token_info};
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::contract::{execute, instantiate, migrate, query, query_token_info};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
     // this will set up the instantiation for other tests
     fn do_instantiate(mut deps: DepsMut, addr: &str, amount: Uint128) -> TokenInfoResponse {
@@ -101,6 +193,7 @@ mod tests {
             }],
             mint: None,
             marketing: None,
+            ..InstantiateMsg::default()
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -122,7 +215,9 @@ mod tests {
         do_instantiate(deps.as_mut(), &owner, Uint128::new(12340000));
 
         // no allowance to start
-        let allowances = query_owner_allowances(deps.as_ref(), owner.clone(), None, None).unwrap();
+        let allowances =
+            query_owner_allowances(deps.as_ref(), mock_env(), owner.clone(), None, None, None)
+                .unwrap();
         assert_eq!(allowances.allowances, vec![]);
 
         // set allowance with height expiration
@@ -145,12 +240,21 @@ mod tests {
         execute(deps.as_mut(), env, info, msg).unwrap();
 
         // query list gets 2
-        let allowances = query_owner_allowances(deps.as_ref(), owner.clone(), None, None).unwrap();
+        let allowances =
+            query_owner_allowances(deps.as_ref(), mock_env(), owner.clone(), None, None, None)
+                .unwrap();
         assert_eq!(allowances.allowances.len(), 2);
 
         // first one is spender1 (order of CanonicalAddr uncorrelated with String)
-        let allowances =
-            query_owner_allowances(deps.as_ref(), owner.clone(), None, Some(1)).unwrap();
+        let allowances = query_owner_allowances(
+            deps.as_ref(),
+            mock_env(),
+            owner.clone(),
+            None,
+            Some(1),
+            None,
+        )
+        .unwrap();
         assert_eq!(allowances.allowances.len(), 1);
         let allow = &allowances.allowances[0];
         assert_eq!(&allow.spender, &spender1);
@@ -160,9 +264,11 @@ mod tests {
         // next one is spender2
         let allowances = query_owner_allowances(
             deps.as_ref(),
+            mock_env(),
             owner,
             Some(allow.spender.clone()),
             Some(10000),
+            None,
         )
         .unwrap();
         assert_eq!(allowances.allowances.len(), 1);
@@ -187,7 +293,8 @@ mod tests {
 
         // no allowance to start
         let allowances =
-            query_spender_allowances(deps.as_ref(), spender.clone(), None, None).unwrap();
+            query_spender_allowances(deps.as_ref(), mock_env(), spender.clone(), None, None, None)
+                .unwrap();
         assert_eq!(allowances.allowances, vec![]);
 
         // set allowance with height expiration
@@ -218,6 +325,7 @@ mod tests {
             spender: spender.clone(),
             start_after: None,
             limit: None,
+            include_expired: None,
         };
         let allowances: AllSpenderAllowancesResponse =
             from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
@@ -228,6 +336,7 @@ mod tests {
             spender: spender.clone(),
             start_after: None,
             limit: Some(1),
+            include_expired: None,
         };
         let allowances: AllSpenderAllowancesResponse =
             from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
@@ -239,17 +348,37 @@ mod tests {
 
         // other one is owner2
         let msg = QueryMsg::AllSpenderAllowances {
-            spender,
-            start_after: Some(owner1),
+            spender: spender.clone(),
+            start_after: Some(owner1.clone()),
             limit: Some(10000),
+            include_expired: None,
         };
         let allowances: AllSpenderAllowancesResponse =
-            from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+            from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
         assert_eq!(allowances.allowances.len(), 1);
         let allow = &allowances.allowances[0];
         assert_eq!(&allow.owner, &owner2);
         assert_eq!(&allow.expires, &Expiration::Never {});
         assert_eq!(&allow.allowance, &allow2);
+
+        // once expired, owner1 is only listed on request
+        let mut env = env;
+        env.block.height = 123_456;
+        let allowances = query_spender_allowances(
+            deps.as_ref(),
+            env.clone(),
+            spender.clone(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(allowances.allowances.len(), 1);
+        assert_eq!(&allowances.allowances[0].owner, &owner2);
+        let allowances =
+            query_spender_allowances(deps.as_ref(), env, spender, None, None, Some(true)).unwrap();
+        assert_eq!(allowances.allowances.len(), 2);
+        assert_eq!(&allowances.allowances[0].owner, &owner1);
     }
 
     #[test]
@@ -275,6 +404,7 @@ mod tests {
             ExecuteMsg::Transfer {
                 recipient: acct2,
                 amount: Uint128::new(222222),
+                memo: None,
             },
         )
         .unwrap();
@@ -285,6 +415,7 @@ mod tests {
             ExecuteMsg::Transfer {
                 recipient: acct3,
                 amount: Uint128::new(333333),
+                memo: None,
             },
         )
         .unwrap();
@@ -295,6 +426,7 @@ mod tests {
             ExecuteMsg::Transfer {
                 recipient: acct4,
                 amount: Uint128::new(444444),
+                memo: None,
             },
         )
         .unwrap();
@@ -316,4 +448,115 @@ mod tests {
                 .unwrap();
         assert_eq!(accounts.accounts, expected_order[3..].to_vec());
     }
+
+    fn coin(address: &str, amount: u128) -> Cw20Coin {
+        Cw20Coin {
+            address: address.into(),
+            amount: Uint128::new(amount),
+        }
+    }
+
+    #[test]
+    fn query_balances_and_top_holders_works() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+
+        let info = mock_info("owner", &[]);
+        for (recipient, amount) in [("zebra", 300u128), ("acct", 100), ("nice", 300)] {
+            let msg = ExecuteMsg::Transfer {
+                recipient: recipient.into(),
+                amount: Uint128::new(amount),
+                memo: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        // ordered by address
+        let balances = query_all_balances(deps.as_ref(), None, Some(3)).unwrap();
+        assert_eq!(
+            balances.balances,
+            vec![coin("acct", 100), coin("nice", 300), coin("owner", 300)]
+        );
+        let balances = query_all_balances(deps.as_ref(), Some("owner".into()), None).unwrap();
+        assert_eq!(balances.balances, vec![coin("zebra", 300)]);
+
+        // ordered by balance, the index follows every transfer
+        let holders = query_top_holders(deps.as_ref(), Some(3), None).unwrap();
+        assert_eq!(holders.holders.len(), 3);
+        assert!(holders
+            .holders
+            .iter()
+            .all(|h| h.amount == Uint128::new(300)));
+        let holders =
+            query_top_holders(deps.as_ref(), None, Some(holders.holders[2].clone())).unwrap();
+        assert_eq!(holders.holders, vec![coin("acct", 100)]);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: "acct".into(),
+            amount: Uint128::new(250),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("zebra", &[]), msg).unwrap();
+        let holders = query_top_holders(deps.as_ref(), Some(2), None).unwrap();
+        assert_eq!(holders.holders, vec![coin("acct", 350), coin("owner", 300)]);
+    }
+
+    #[test]
+    fn top_holders_paginates_through_equal_balances() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(250));
+
+        let info = mock_info("owner", &[]);
+        for recipient in ["bob", "carl", "dave"] {
+            let msg = ExecuteMsg::Transfer {
+                recipient: recipient.into(),
+                amount: Uint128::new(50),
+                memo: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        // 100/50/50/50, the page ends in the middle of the 50s
+        let page = query_top_holders(deps.as_ref(), Some(2), None).unwrap();
+        assert_eq!(page.holders, vec![coin("owner", 100), coin("dave", 50)]);
+        let last = page.holders.last().cloned();
+        let page = query_top_holders(deps.as_ref(), Some(2), last).unwrap();
+        assert_eq!(page.holders, vec![coin("carl", 50), coin("bob", 50)]);
+        let last = page.holders.last().cloned();
+        let page = query_top_holders(deps.as_ref(), Some(2), last).unwrap();
+        assert_eq!(page.holders, vec![]);
+    }
+
+    #[test]
+    fn migrate_builds_balance_index() {
+        let mut deps = mock_dependencies_with_balance(&coins(2, "token"));
+        do_instantiate(deps.as_mut(), "owner", Uint128::new(1000));
+        let msg = ExecuteMsg::Transfer {
+            recipient: "other".into(),
+            amount: Uint128::new(10),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+        // drop the index, as for contracts deployed before it was introduced
+        let accounts = balances()
+            .range(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        for (addr, balance) in accounts {
+            balances()
+                .idx
+                .amount
+                .remove(&mut deps.storage, addr.as_bytes(), &balance)
+                .unwrap();
+        }
+        assert_eq!(
+            query_top_holders(deps.as_ref(), None, None).unwrap(),
+            TopHoldersResponse::default()
+        );
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        let holders = query_top_holders(deps.as_ref(), None, None).unwrap();
+        assert_eq!(holders.holders, vec![coin("owner", 990), coin("other", 10)]);
+    }
 }
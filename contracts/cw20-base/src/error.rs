@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw20::LogoError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +10,12 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Token is paused")]
+    Paused {},
+
+    #[error("Address {address} is not allowed to send or receive tokens")]
+    NotCompliant { address: String },
+
     #[error("Cannot set to own account")]
     CannotSetOwnAccount {},
 
@@ -24,18 +31,66 @@ pub enum ContractError {
     #[error("Minting cannot exceed the cap")]
     CannotExceedCap {},
 
-    #[error("Logo binary data exceeds 5KB limit")]
-    LogoTooBig {},
+    #[error("Minting cannot exceed the minter's cap")]
+    MinterCapExceeded {},
+
+    #[error("Minting cannot exceed the minter's quota for this period")]
+    MinterQuotaExceeded {},
+
+    #[error("Minter quota period must not be zero")]
+    InvalidMinterQuota {},
+
+    #[error("Token is not mintable")]
+    NotMintable {},
+
+    #[error("Cap cannot be lower than the total supply")]
+    CapBelowSupply {},
+
+    #[error("Cannot remove the capped minter while other minters are registered")]
+    MintersRegistered {},
+
+    #[error("{0}")]
+    Logo(#[from] LogoError),
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
 
-    #[error("Invalid xml preamble for SVG")]
-    InvalidXmlPreamble {},
+    #[error("Permit public key must be a compressed secp256k1 key")]
+    InvalidPermitPubkey {},
 
-    #[error("Invalid png header")]
-    InvalidPngHeader {},
+    #[error("Invalid permit nonce, expected {expected}")]
+    InvalidPermitNonce { expected: u64 },
+
+    #[error("Allowance period must not be zero")]
+    InvalidAllowancePeriod {},
+
+    #[error("Allowance does not cover this recipient")]
+    RecipientNotAllowed {},
 
     #[error("Invalid expiration value")]
     InvalidExpiration {},
 
     #[error("Duplicate initial balance addresses")]
     DuplicateInitialBalanceAddresses {},
+
+    #[error("Batch must not be empty")]
+    EmptyBatch {},
+
+    #[error("Duplicate addresses in batch")]
+    DuplicateBatchAddresses {},
+
+    #[error("Fee rate must be lower than 1")]
+    InvalidFeeRate {},
+
+    #[error("Cannot move tokens that have not vested yet")]
+    LockedTokens {},
+
+    #[error("Duplicate initial vesting addresses")]
+    DuplicateInitialVestingAddresses {},
+
+    #[error("No initial balance to vest for {address}")]
+    VestingWithoutBalance { address: String },
+
+    #[error("Memo must not be longer than {max} bytes")]
+    MemoTooLong { max: u32 },
 }
@@ -0,0 +1,296 @@
+use cosmwasm_std::{
+    Addr, Api, Attribute, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Storage,
+    Uint128,
+};
+use cw20::{FeeConfigResponse, TransferFee};
+
+use crate::error::ContractError;
+use crate::state::{balances, FeeConfig, FEE_ADMIN, FEE_CONFIG};
+
+/// Validates the rate and addresses of a fee as passed in a message.
+pub fn validate_fee(api: &dyn Api, fee: TransferFee) -> Result<FeeConfig, ContractError> {
+    if fee.rate >= Decimal::one() {
+        return Err(ContractError::InvalidFeeRate {});
+    }
+    Ok(FeeConfig {
+        rate: fee.rate,
+        recipient: api.addr_validate(&fee.recipient)?,
+        exempt: fee
+            .exempt
+            .iter()
+            .map(|addr| api.addr_validate(addr))
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+/// Credits the fee on a transfer of `amount` from `from` to `to` to the fee recipient and
+/// returns it. The caller must credit `to` with the remainder only.
+/// Returns zero if no fee is configured or either party is exempt.
+pub fn charge_transfer_fee(
+    storage: &mut dyn Storage,
+    height: u64,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let config = match FEE_CONFIG.may_load(storage)? {
+        Some(config) => config,
+        None => return Ok(Uint128::zero()),
+    };
+    if config.exempt.iter().any(|addr| addr == from || addr == to) {
+        return Ok(Uint128::zero());
+    }
+
+    let fee = amount * config.rate;
+    if !fee.is_zero() {
+        balances().update(
+            storage,
+            &config.recipient,
+            height,
+            |balance: Option<Uint128>| -> StdResult<_> {
+                Ok(balance.unwrap_or_default().checked_add(fee)?)
+            },
+        )?;
+    }
+    Ok(fee)
+}
+
+/// Attributes describing a charged fee, empty if `fee` is zero
+pub fn fee_attributes(amount: Uint128, fee: Uint128) -> Vec<Attribute> {
+    if fee.is_zero() {
+        return vec![];
+    }
+    vec![
+        Attribute::new("net_amount", amount - fee),
+        Attribute::new("fee", fee),
+    ]
+}
+
+pub fn execute_update_fee(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    fee: Option<TransferFee>,
+) -> Result<Response, ContractError> {
+    let admin = FEE_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match fee {
+        Some(fee) => FEE_CONFIG.save(deps.storage, &validate_fee(deps.api, fee)?)?,
+        None => FEE_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_fee"))
+}
+
+pub fn query_fee_config(deps: Deps) -> StdResult<FeeConfigResponse> {
+    let admin = FEE_ADMIN.may_load(deps.storage)?.map(Into::into);
+    let fee = FEE_CONFIG.may_load(deps.storage)?.map(|c| TransferFee {
+        rate: c.rate,
+        recipient: c.recipient.into(),
+        exempt: c.exempt.into_iter().map(Into::into).collect(),
+    });
+    Ok(FeeConfigResponse { admin, fee })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Binary, CosmosMsg, SubMsg, WasmMsg};
+    use cw20::{Cw20Coin, Cw20ReceiveMsg};
+
+    use crate::allowances::query_allowance;
+    use crate::contract::execute;
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::test_helpers::{get_balance, instantiate_token, token_msg, OWNER};
+
+    const ADMIN: &str = "fee_admin";
+    const TREASURY: &str = "treasury";
+
+    fn fee(percent: u64, exempt: &[&str]) -> TransferFee {
+        TransferFee {
+            rate: Decimal::percent(percent),
+            recipient: TREASURY.into(),
+            exempt: exempt.iter().map(|addr| addr.to_string()).collect(),
+        }
+    }
+
+    fn do_instantiate(deps: DepsMut, transfer_fee: Option<TransferFee>) {
+        let msg = InstantiateMsg {
+            transfer_fee,
+            fee_admin: Some(ADMIN.into()),
+            ..token_msg()
+        };
+        instantiate_token(deps, mock_env(), msg);
+    }
+
+    #[test]
+    fn rejects_invalid_rate() {
+        let mut deps = mock_dependencies();
+        let err = validate_fee(&deps.api, fee(100, &[])).unwrap_err();
+        assert_eq!(err, ContractError::InvalidFeeRate {});
+
+        do_instantiate(deps.as_mut(), None);
+        let msg = ExecuteMsg::UpdateFee {
+            fee: Some(fee(150, &[])),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidFeeRate {});
+    }
+
+    #[test]
+    fn only_admin_updates_fee() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+        assert_eq!(
+            query_fee_config(deps.as_ref()).unwrap(),
+            FeeConfigResponse {
+                admin: Some(ADMIN.into()),
+                fee: None,
+            }
+        );
+
+        let msg = ExecuteMsg::UpdateFee {
+            fee: Some(fee(1, &[])),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(
+            query_fee_config(deps.as_ref()).unwrap().fee,
+            Some(fee(1, &[]))
+        );
+
+        // removing the fee makes transfers free again
+        let msg = ExecuteMsg::UpdateFee { fee: None };
+        execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+        assert_eq!(query_fee_config(deps.as_ref()).unwrap().fee, None);
+    }
+
+    #[test]
+    fn transfer_charges_fee() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), Some(fee(10, &["exempt"])));
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: "rcpt".into(),
+            amount: Uint128::new(105),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes[3..],
+            [
+                Attribute::new("amount", "105"),
+                Attribute::new("net_amount", "95"),
+                Attribute::new("fee", "10"),
+            ]
+        );
+        assert_eq!(get_balance(deps.as_ref(), OWNER), Uint128::new(895));
+        assert_eq!(get_balance(deps.as_ref(), "rcpt"), Uint128::new(95));
+        assert_eq!(get_balance(deps.as_ref(), TREASURY), Uint128::new(10));
+
+        // exempt parties pay nothing, whether sending or receiving
+        let msg = ExecuteMsg::Transfer {
+            recipient: "exempt".into(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert_eq!(res.attributes.len(), 4);
+        let msg = ExecuteMsg::Transfer {
+            recipient: "rcpt".into(),
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("exempt", &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "rcpt"), Uint128::new(195));
+        assert_eq!(get_balance(deps.as_ref(), TREASURY), Uint128::new(10));
+    }
+
+    #[test]
+    fn send_from_charges_fee() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), Some(fee(5, &[])));
+
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(300),
+            expires: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+
+        let send_msg = Binary::from(r#"{"some":123}"#.as_bytes());
+        let msg = ExecuteMsg::SendFrom {
+            owner: OWNER.into(),
+            contract: "contract".into(),
+            amount: Uint128::new(200),
+            msg: send_msg.clone(),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+
+        // the contract is told about the amount it actually received
+        let binary_msg = Cw20ReceiveMsg {
+            sender: "spender".into(),
+            amount: Uint128::new(190),
+            msg: send_msg,
+            memo: None,
+        }
+        .into_binary()
+        .unwrap();
+        assert_eq!(
+            res.messages[0],
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "contract".into(),
+                msg: binary_msg,
+                funds: vec![],
+            }))
+        );
+        assert_eq!(get_balance(deps.as_ref(), "contract"), Uint128::new(190));
+        assert_eq!(get_balance(deps.as_ref(), TREASURY), Uint128::new(10));
+
+        // the allowance is charged the full amount
+        let allowance =
+            query_allowance(deps.as_ref(), mock_env(), OWNER.into(), "spender".into()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(100));
+    }
+
+    #[test]
+    fn batch_charges_fee_per_recipient() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), Some(fee(10, &[])));
+
+        let msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                Cw20Coin {
+                    address: "first".into(),
+                    amount: Uint128::new(100),
+                },
+                Cw20Coin {
+                    address: "second".into(),
+                    amount: Uint128::new(9),
+                },
+            ],
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), OWNER), Uint128::new(891));
+        assert_eq!(get_balance(deps.as_ref(), "first"), Uint128::new(90));
+        // fees are rounded down
+        assert_eq!(get_balance(deps.as_ref(), "second"), Uint128::new(9));
+        assert_eq!(get_balance(deps.as_ref(), TREASURY), Uint128::new(10));
+    }
+}
@@ -14,11 +14,19 @@ For more information on this contract, please check out the
 [README](https://github.com/CosmWasm/cw-plus/blob/main/contracts/cw20-base/README.md).
 */
 
+pub mod admin;
 pub mod allowances;
+pub mod clawback;
+pub mod compliance;
 pub mod contract;
 pub mod enumerable;
 mod error;
+pub mod fee;
+pub mod memo;
+pub mod minters;
 pub mod msg;
 pub mod state;
+mod test_helpers;
+pub mod vesting;
 
 pub use crate::error::ContractError;
@@ -0,0 +1,156 @@
+use cosmwasm_std::{Attribute, Storage};
+
+use crate::error::ContractError;
+use crate::state::MAX_MEMO_LENGTH;
+
+/// The limit used when none was set on instantiation.
+pub const DEFAULT_MAX_MEMO_LENGTH: u32 = 256;
+
+/// Rejects a memo longer than the configured maximum (in bytes).
+pub fn ensure_valid_memo(storage: &dyn Storage, memo: Option<&str>) -> Result<(), ContractError> {
+    if let Some(memo) = memo {
+        let max = MAX_MEMO_LENGTH
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_MAX_MEMO_LENGTH);
+        if memo.len() > max as usize {
+            return Err(ContractError::MemoTooLong { max });
+        }
+    }
+    Ok(())
+}
+
+/// The `memo` attribute, empty if no memo was given
+pub fn memo_attributes(memo: Option<&str>) -> Vec<Attribute> {
+    memo.map(|memo| Attribute::new("memo", memo))
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Binary, CosmosMsg, DepsMut, SubMsg, Uint128, WasmMsg};
+    use cw20::events::TransferEvent;
+    use cw20::Cw20ReceiveMsg;
+
+    use crate::contract::execute;
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::test_helpers::{instantiate_token, token_msg, OWNER};
+
+    fn do_instantiate(deps: DepsMut, max_memo_length: Option<u32>) {
+        let msg = InstantiateMsg {
+            max_memo_length,
+            ..token_msg()
+        };
+        instantiate_token(deps, mock_env(), msg);
+    }
+
+    #[test]
+    fn memo_is_emitted() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+
+        let msg = ExecuteMsg::Transfer {
+            recipient: "exchange".into(),
+            amount: Uint128::new(100),
+            memo: Some("user 42".into()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes.last(),
+            Some(&Attribute::new("memo", "user 42"))
+        );
+        assert_eq!(
+            TransferEvent::try_from(&res.events[0]).unwrap(),
+            TransferEvent {
+                from: OWNER.into(),
+                to: "exchange".into(),
+                amount: Uint128::new(100),
+                by: None,
+                memo: Some("user 42".into()),
+            }
+        );
+
+        // and passed on to the receiving contract
+        let msg = ExecuteMsg::Send {
+            contract: "exchange".into(),
+            amount: Uint128::new(100),
+            msg: Binary::default(),
+            memo: Some("user 43".into()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes.last(),
+            Some(&Attribute::new("memo", "user 43"))
+        );
+        let receive = Cw20ReceiveMsg {
+            sender: OWNER.into(),
+            amount: Uint128::new(100),
+            msg: Binary::default(),
+            memo: Some("user 43".into()),
+        };
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "exchange".into(),
+                msg: receive.into_binary().unwrap(),
+                funds: vec![],
+            }))]
+        );
+
+        // no memo, no attribute
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+            memo: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(OWNER, &[]), msg).unwrap();
+        assert!(res.attributes.iter().all(|attr| attr.key != "memo"));
+    }
+
+    #[test]
+    fn memo_length_is_limited() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), Some(8));
+
+        let transfer = |memo: &str| ExecuteMsg::Transfer {
+            recipient: "exchange".into(),
+            amount: Uint128::new(100),
+            memo: Some(memo.into()),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            transfer("12345678"),
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            transfer("123456789"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MemoTooLong { max: 8 });
+
+        // without a configured limit, the default applies
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+        let memo = "x".repeat(DEFAULT_MAX_MEMO_LENGTH as usize + 1);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(OWNER, &[]),
+            transfer(&memo),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MemoTooLong {
+                max: DEFAULT_MAX_MEMO_LENGTH
+            }
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use cosmwasm_std::{
+    Addr, BlockInfo, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult,
+    Storage, Timestamp, Uint128,
+};
+use cw20::{AllMintersResponse, MinterInfo, MinterQuota};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::state::{MinterState, MINTERS, MINT_ADMIN};
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+fn ensure_mint_admin(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let admin = MINT_ADMIN
+        .may_load(storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if admin != *sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+fn validate_quota(quota: &Option<MinterQuota>) -> Result<(), ContractError> {
+    match quota {
+        Some(MinterQuota { period: 0, .. }) => Err(ContractError::InvalidMinterQuota {}),
+        _ => Ok(()),
+    }
+}
+
+/// Returns `ContractError::Unauthorized` unless `minter` is a registered minter.
+pub fn ensure_registered_minter(storage: &dyn Storage, minter: &Addr) -> Result<(), ContractError> {
+    if !MINTERS.has(storage, minter) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Releases the part of the used quota that has decayed since it was last updated. Rounding
+/// only ever releases too little, and the update time is kept until something is released, so
+/// frequent small mints do not stop the decay.
+fn release_quota(state: &mut MinterState, quota: &MinterQuota, now: Timestamp) {
+    let elapsed = now.seconds().saturating_sub(state.quota_updated.seconds());
+    let released = quota
+        .amount
+        .multiply_ratio(elapsed.min(quota.period), quota.period);
+    if released >= state.quota_used {
+        state.quota_used = Uint128::zero();
+        state.quota_updated = now;
+    } else if !released.is_zero() {
+        state.quota_used -= released;
+        state.quota_updated = now;
+    }
+}
+
+/// Charges `amount` against the cap and the rolling quota of a registered minter.
+pub fn consume_mint_allowance(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    minter: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let mut state = MINTERS
+        .may_load(storage, minter)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    state.minted = state
+        .minted
+        .checked_add(amount)
+        .map_err(StdError::overflow)?;
+    if let Some(cap) = state.cap {
+        if state.minted > cap {
+            return Err(ContractError::MinterCapExceeded {});
+        }
+    }
+
+    if let Some(quota) = state.quota.clone() {
+        release_quota(&mut state, &quota, block.time);
+        state.quota_used = state
+            .quota_used
+            .checked_add(amount)
+            .map_err(StdError::overflow)?;
+        if state.quota_used > quota.amount {
+            return Err(ContractError::MinterQuotaExceeded {});
+        }
+    }
+
+    MINTERS.save(storage, minter, &state)?;
+    Ok(())
+}
+
+pub fn execute_add_minter(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    minter: String,
+    cap: Option<Uint128>,
+    quota: Option<MinterQuota>,
+) -> Result<Response, ContractError> {
+    ensure_mint_admin(deps.storage, &info.sender)?;
+    validate_quota(&quota)?;
+    let minter_addr = deps.api.addr_validate(&minter)?;
+
+    // registering a minter again only updates its limits, it keeps counting what was minted
+    MINTERS.update(deps.storage, &minter_addr, |state| -> StdResult<_> {
+        let state = match state {
+            Some(state) => MinterState {
+                cap,
+                quota,
+                ..state
+            },
+            None => MinterState {
+                cap,
+                minted: Uint128::zero(),
+                quota,
+                quota_used: Uint128::zero(),
+                quota_updated: env.block.time,
+            },
+        };
+        Ok(state)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_minter")
+        .add_attribute("minter", minter))
+}
+
+pub fn execute_remove_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    minter: String,
+) -> Result<Response, ContractError> {
+    ensure_mint_admin(deps.storage, &info.sender)?;
+    let minter_addr = deps.api.addr_validate(&minter)?;
+    MINTERS.remove(deps.storage, &minter_addr);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_minter")
+        .add_attribute("minter", minter))
+}
+
+pub fn execute_update_minter_quota(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    minter: String,
+    cap: Option<Uint128>,
+    quota: Option<MinterQuota>,
+) -> Result<Response, ContractError> {
+    ensure_mint_admin(deps.storage, &info.sender)?;
+    validate_quota(&quota)?;
+    let minter_addr = deps.api.addr_validate(&minter)?;
+
+    let mut state = MINTERS.load(deps.storage, &minter_addr)?;
+    state.cap = cap;
+    state.quota = quota;
+    MINTERS.save(deps.storage, &minter_addr, &state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_minter_quota")
+        .add_attribute("minter", minter))
+}
+
+pub fn query_all_minters(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllMintersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into_bytes()));
+
+    let minters = MINTERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(minter, mut state)| {
+                match state.quota.clone() {
+                    Some(quota) => release_quota(&mut state, &quota, env.block.time),
+                    None => state.quota_used = Uint128::zero(),
+                }
+                MinterInfo {
+                    minter: minter.into(),
+                    cap: state.cap,
+                    minted: state.minted,
+                    quota: state.quota,
+                    quota_used: state.quota_used,
+                }
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(AllMintersResponse { minters })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_info};
+    use cw20::MinterResponse;
+
+    use crate::contract::{execute, query_minter};
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::test_helpers::{env_at, get_balance, instantiate_token, token_msg};
+
+    const ADMIN: &str = "mint_admin";
+    const MINTER: &str = "minter";
+    const BRIDGE: &str = "bridge";
+
+    fn do_instantiate(deps: DepsMut, cap: Option<Uint128>) {
+        let msg = InstantiateMsg {
+            initial_balances: vec![],
+            mint: Some(MinterResponse {
+                minter: MINTER.into(),
+                cap,
+            }),
+            mint_admin: Some(ADMIN.into()),
+            ..token_msg()
+        };
+        instantiate_token(deps, env_at(0), msg);
+    }
+
+    fn add_bridge(deps: DepsMut, cap: Option<u128>, quota: Option<MinterQuota>) {
+        let msg = ExecuteMsg::AddMinter {
+            minter: BRIDGE.into(),
+            cap: cap.map(Uint128::new),
+            quota,
+        };
+        execute(deps, env_at(0), mock_info(ADMIN, &[]), msg).unwrap();
+    }
+
+    fn mint(deps: DepsMut, env: Env, minter: &str, amount: u128) -> Result<(), ContractError> {
+        let msg = ExecuteMsg::Mint {
+            recipient: "rcpt".into(),
+            amount: Uint128::new(amount),
+        };
+        execute(deps, env, mock_info(minter, &[]), msg).map(|_| ())
+    }
+
+    #[test]
+    fn only_admin_manages_minters() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+
+        let msg = ExecuteMsg::AddMinter {
+            minter: BRIDGE.into(),
+            cap: None,
+            quota: None,
+        };
+        let err = execute(deps.as_mut(), env_at(0), mock_info(MINTER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let msg = ExecuteMsg::AddMinter {
+            minter: BRIDGE.into(),
+            cap: None,
+            quota: Some(MinterQuota {
+                amount: Uint128::new(10),
+                period: 0,
+            }),
+        };
+        let err = execute(deps.as_mut(), env_at(0), mock_info(ADMIN, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidMinterQuota {});
+
+        // unregistered addresses cannot mint
+        let err = mint(deps.as_mut(), env_at(0), BRIDGE, 1).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        add_bridge(deps.as_mut(), None, None);
+        mint(deps.as_mut(), env_at(0), BRIDGE, 1).unwrap();
+
+        let msg = ExecuteMsg::RemoveMinter {
+            minter: BRIDGE.into(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env_at(0),
+            mock_info(BRIDGE, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), env_at(0), mock_info(ADMIN, &[]), msg).unwrap();
+        let err = mint(deps.as_mut(), env_at(0), BRIDGE, 1).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // the primary minter is unaffected
+        mint(deps.as_mut(), env_at(0), MINTER, 1).unwrap();
+        assert_eq!(
+            query_minter(deps.as_ref()).unwrap().unwrap().minter,
+            MINTER.to_string()
+        );
+    }
+
+    #[test]
+    fn minter_cap_and_global_cap() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), Some(Uint128::new(100)));
+        add_bridge(deps.as_mut(), Some(60), None);
+
+        mint(deps.as_mut(), env_at(0), BRIDGE, 50).unwrap();
+        let err = mint(deps.as_mut(), env_at(0), BRIDGE, 11).unwrap_err();
+        assert_eq!(err, ContractError::MinterCapExceeded {});
+
+        // the supply cap holds for all minters together
+        mint(deps.as_mut(), env_at(0), MINTER, 45).unwrap();
+        let err = mint(deps.as_mut(), env_at(0), BRIDGE, 10).unwrap_err();
+        assert_eq!(err, ContractError::CannotExceedCap {});
+        mint(deps.as_mut(), env_at(0), BRIDGE, 5).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "rcpt"), Uint128::new(100));
+
+        // raising the cap of the minter keeps what it minted so far
+        let msg = ExecuteMsg::UpdateMinterQuota {
+            minter: BRIDGE.into(),
+            cap: Some(Uint128::new(70)),
+            quota: None,
+        };
+        execute(deps.as_mut(), env_at(0), mock_info(ADMIN, &[]), msg).unwrap();
+        let minters = query_all_minters(deps.as_ref(), env_at(0), None, None).unwrap();
+        assert_eq!(
+            minters.minters,
+            vec![MinterInfo {
+                minter: BRIDGE.into(),
+                cap: Some(Uint128::new(70)),
+                minted: Uint128::new(55),
+                quota: None,
+                quota_used: Uint128::zero(),
+            }]
+        );
+
+        // the capped primary minter cannot be dropped while others depend on its cap
+        let msg = ExecuteMsg::UpdateMinter { new_minter: None };
+        let err = execute(deps.as_mut(), env_at(0), mock_info(MINTER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::MintersRegistered {});
+    }
+
+    fn quota_used(deps: Deps, seconds: u64) -> Uint128 {
+        query_all_minters(deps, env_at(seconds), None, None)
+            .unwrap()
+            .minters[0]
+            .quota_used
+    }
+
+    #[test]
+    fn quota_is_released_gradually() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+        // released at one token per second
+        let quota = MinterQuota {
+            amount: Uint128::new(100),
+            period: 100,
+        };
+        add_bridge(deps.as_mut(), None, Some(quota.clone()));
+
+        mint(deps.as_mut(), env_at(10), BRIDGE, 60).unwrap();
+        mint(deps.as_mut(), env_at(10), BRIDGE, 40).unwrap();
+        let err = mint(deps.as_mut(), env_at(10), BRIDGE, 1).unwrap_err();
+        assert_eq!(err, ContractError::MinterQuotaExceeded {});
+
+        // half of it is back after half the period
+        assert_eq!(quota_used(deps.as_ref(), 60), Uint128::new(50));
+        mint(deps.as_mut(), env_at(60), BRIDGE, 50).unwrap();
+        let err = mint(deps.as_mut(), env_at(60), BRIDGE, 1).unwrap_err();
+        assert_eq!(err, ContractError::MinterQuotaExceeded {});
+
+        // nothing resets at the end of the first period: just before and just after it,
+        // only what was released in between can be minted
+        mint(deps.as_mut(), env_at(99), BRIDGE, 39).unwrap();
+        let err = mint(deps.as_mut(), env_at(101), BRIDGE, 100).unwrap_err();
+        assert_eq!(err, ContractError::MinterQuotaExceeded {});
+        mint(deps.as_mut(), env_at(101), BRIDGE, 2).unwrap();
+        assert_eq!(quota_used(deps.as_ref(), 101), Uint128::new(100));
+
+        // everything is back after a full period
+        assert_eq!(quota_used(deps.as_ref(), 201), Uint128::zero());
+        mint(deps.as_mut(), env_at(201), BRIDGE, 100).unwrap();
+        assert_eq!(
+            query_all_minters(deps.as_ref(), env_at(201), None, None)
+                .unwrap()
+                .minters[0],
+            MinterInfo {
+                minter: BRIDGE.into(),
+                cap: None,
+                minted: Uint128::new(291),
+                quota: Some(quota),
+                quota_used: Uint128::new(100),
+            }
+        );
+    }
+
+    #[test]
+    fn frequent_mints_do_not_stop_release() {
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), None);
+        // released at one token every ten seconds
+        let quota = MinterQuota {
+            amount: Uint128::new(10),
+            period: 100,
+        };
+        add_bridge(deps.as_mut(), None, Some(quota));
+
+        mint(deps.as_mut(), env_at(0), BRIDGE, 9).unwrap();
+        // too early to release a whole token, but the five seconds still count
+        mint(deps.as_mut(), env_at(5), BRIDGE, 1).unwrap();
+        mint(deps.as_mut(), env_at(10), BRIDGE, 1).unwrap();
+        let err = mint(deps.as_mut(), env_at(10), BRIDGE, 1).unwrap_err();
+        assert_eq!(err, ContractError::MinterQuotaExceeded {});
+    }
+}
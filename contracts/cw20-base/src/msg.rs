@@ -1,6 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{StdError, StdResult, Uint128};
-use cw20::{Cw20Coin, Logo, MinterResponse};
+use cw20::{ComplianceMode, Cw20Coin, Logo, MinterResponse, TransferFee, VestingSchedule};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -15,14 +15,47 @@ pub struct InstantiateMarketingInfo {
 }
 
 #[cw_serde]
-#[cfg_attr(test, derive(Default))]
+pub struct InstantiateComplianceInfo {
+    /// The address allowed to update the blocklist and allowlist
+    pub admin: String,
+    pub mode: ComplianceMode,
+}
+
+/// Locks the initial balance of `address` until it vests
+#[cw_serde]
+pub struct InitialVesting {
+    pub address: String,
+    pub schedule: VestingSchedule,
+}
+
+#[cw_serde]
+#[derive(Default)]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
     pub initial_balances: Vec<Cw20Coin>,
+    /// Vesting schedules for some of the initial balances. Each locks the whole initial
+    /// balance of its address, which must be listed in `initial_balances`.
+    pub initial_vesting: Option<Vec<InitialVesting>>,
     pub mint: Option<MinterResponse>,
+    /// The address (if any) allowed to register additional minters
+    pub mint_admin: Option<String>,
     pub marketing: Option<InstantiateMarketingInfo>,
+    /// The address (if any) allowed to pause and unpause all token movements
+    pub pauser: Option<String>,
+    /// If set, every transfer, send and mint is checked against the blocklist or allowlist
+    pub compliance: Option<InstantiateComplianceInfo>,
+    /// The fee (if any) charged on every transfer and send
+    pub transfer_fee: Option<TransferFee>,
+    /// The address (if any) allowed to update the transfer fee
+    pub fee_admin: Option<String>,
+    /// The address (if any) allowed to update the name, symbol and cap
+    pub admin: Option<String>,
+    /// The address (if any) allowed to force transfers and burns from any account
+    pub clawback_admin: Option<String>,
+    /// The maximum length of a transfer memo in bytes, 256 if not set
+    pub max_memo_length: Option<u32>,
 }
 
 impl InstantiateMsg {
@@ -32,42 +65,54 @@ impl InstantiateMsg {
 
     pub fn validate(&self) -> StdResult<()> {
         // Check name, symbol, decimals
-        if !self.has_valid_name() {
-            return Err(StdError::generic_err(
-                "Name is not in the expected format (3-50 UTF-8 bytes)",
-            ));
-        }
-        if !self.has_valid_symbol() {
-            return Err(StdError::generic_err(
-                "Ticker symbol is not in expected format [a-zA-Z\\-]{3,12}",
-            ));
-        }
+        validate_name(&self.name)?;
+        validate_symbol(&self.symbol)?;
         if self.decimals > 18 {
             return Err(StdError::generic_err("Decimals must not exceed 18"));
         }
         Ok(())
     }
+}
 
-    fn has_valid_name(&self) -> bool {
-        let bytes = self.name.as_bytes();
-        if bytes.len() < 3 || bytes.len() > 50 {
-            return false;
-        }
-        true
+/// Checks a token name, as on instantiation
+pub fn validate_name(name: &str) -> StdResult<()> {
+    if !is_valid_name(name) {
+        return Err(StdError::generic_err(
+            "Name is not in the expected format (3-50 UTF-8 bytes)",
+        ));
+    }
+    Ok(())
+}
+
+/// Checks a ticker symbol, as on instantiation
+pub fn validate_symbol(symbol: &str) -> StdResult<()> {
+    if !is_valid_symbol(symbol) {
+        return Err(StdError::generic_err(
+            "Ticker symbol is not in expected format [a-zA-Z\\-]{3,12}",
+        ));
+    }
+    Ok(())
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    if bytes.len() < 3 || bytes.len() > 50 {
+        return false;
     }
+    true
+}
 
-    fn has_valid_symbol(&self) -> bool {
-        let bytes = self.symbol.as_bytes();
-        if bytes.len() < 3 || bytes.len() > 12 {
+fn is_valid_symbol(symbol: &str) -> bool {
+    let bytes = symbol.as_bytes();
+    if bytes.len() < 3 || bytes.len() > 12 {
+        return false;
+    }
+    for byte in bytes.iter() {
+        if (*byte != 45) && (*byte < 65 || *byte > 90) && (*byte < 97 || *byte > 122) {
             return false;
         }
-        for byte in bytes.iter() {
-            if (*byte != 45) && (*byte < 65 || *byte > 90) && (*byte < 97 || *byte > 122) {
-                return false;
-            }
-        }
-        true
     }
+    true
 }
 
 #[cw_serde]
@@ -76,9 +121,16 @@ pub enum QueryMsg {
     /// Returns the current balance of the given address, 0 if unset.
     #[returns(cw20::BalanceResponse)]
     Balance { address: String },
+    /// Returns the balance of the given address at the beginning of the block at the given
+    /// height, 0 if unset.
+    #[returns(cw20::BalanceResponse)]
+    BalanceAt { address: String, height: u64 },
     /// Returns metadata on the contract - name, decimals, supply, etc.
     #[returns(cw20::TokenInfoResponse)]
     TokenInfo {},
+    /// Returns the total supply at the beginning of the block at the given height.
+    #[returns(TotalSupplyResponse)]
+    TotalSupplyAt { height: u64 },
     /// Only with "mintable" extension.
     /// Returns who can mint and the hard cap on maximum tokens after minting.
     #[returns(cw20::MinterResponse)]
@@ -89,19 +141,25 @@ pub enum QueryMsg {
     Allowance { owner: String, spender: String },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this owner has approved. Supports pagination.
+    /// Expired allowances are skipped unless `include_expired` is set.
     #[returns(cw20::AllAllowancesResponse)]
     AllAllowances {
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        include_expired: Option<bool>,
     },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this spender has been granted. Supports pagination.
+    /// Expired allowances are skipped unless `include_expired` is set.
     #[returns(cw20::AllSpenderAllowancesResponse)]
     AllSpenderAllowances {
         spender: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        include_expired: Option<bool>,
     },
     /// Only with "enumerable" extension
     /// Returns all accounts that have balances. Supports pagination.
@@ -110,6 +168,21 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Only with "enumerable" extension
+    /// Returns all accounts with their balances, ordered by address. Supports pagination.
+    #[returns(cw20::AllBalancesResponse)]
+    AllBalances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "enumerable" extension
+    /// Returns the accounts with the highest balances, largest first. Supports pagination
+    /// by the last holder of the previous page.
+    #[returns(cw20::TopHoldersResponse)]
+    TopHolders {
+        limit: Option<u32>,
+        start_after: Option<cw20::Cw20Coin>,
+    },
     /// Only with "marketing" extension
     /// Returns more metadata on the contract to display in the client:
     /// - description, logo, project url, etc.
@@ -120,6 +193,60 @@ pub enum QueryMsg {
     /// contract.
     #[returns(cw20::DownloadLogoResponse)]
     DownloadLogo {},
+    /// Only with "pausable" extension
+    /// Returns who can pause the token and whether it is currently paused.
+    #[returns(cw20::PauseInfoResponse)]
+    PauseInfo {},
+    /// Only with "compliance" extension
+    /// Returns the compliance admin and mode, or None if compliance checks are disabled.
+    #[returns(Option<cw20::ComplianceInfoResponse>)]
+    ComplianceInfo {},
+    /// Only with "compliance" extension
+    /// Returns the blocked addresses. Supports pagination.
+    #[returns(cw20::AddressListResponse)]
+    Blocklist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "compliance" extension
+    /// Returns the allowed addresses. Supports pagination.
+    #[returns(cw20::AddressListResponse)]
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "fee" extension
+    /// Returns the transfer fee and who can update it.
+    #[returns(cw20::FeeConfigResponse)]
+    FeeConfig {},
+    /// Only with "minters" extension
+    /// Returns the registered minters with their limits and usage. Supports pagination.
+    #[returns(cw20::AllMintersResponse)]
+    AllMinters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "vesting" extension
+    /// Returns the vesting schedule of the given address and how much is still locked.
+    #[returns(cw20::VestingInfoResponse)]
+    VestingInfo { address: String },
+    /// Only with "admin" extension
+    /// Returns who can update the token name, symbol and cap.
+    #[returns(cw20::TokenAdminResponse)]
+    TokenAdmin {},
+    /// Only with "clawback" extension
+    /// Returns who can force transfers and burns, if anyone.
+    #[returns(cw20::ClawbackAdminResponse)]
+    ClawbackAdmin {},
+    /// Only with "permit" extension
+    /// Returns the nonce the next permit signed by the owner must use.
+    #[returns(cw20::PermitNonceResponse)]
+    PermitNonce { owner: String },
+}
+
+#[cw_serde]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -136,15 +263,15 @@ mod tests {
             name: str::repeat("a", 2),
             ..InstantiateMsg::default()
         };
-        assert!(!msg.has_valid_name());
+        assert!(!is_valid_name(&msg.name));
 
         // In the correct length range
         msg.name = str::repeat("a", 3);
-        assert!(msg.has_valid_name());
+        assert!(is_valid_name(&msg.name));
 
         // Too long
         msg.name = str::repeat("a", 51);
-        assert!(!msg.has_valid_name());
+        assert!(!is_valid_name(&msg.name));
     }
 
     #[test]
@@ -154,22 +281,22 @@ mod tests {
             symbol: str::repeat("a", 2),
             ..InstantiateMsg::default()
         };
-        assert!(!msg.has_valid_symbol());
+        assert!(!is_valid_symbol(&msg.symbol));
 
         // In the correct length range
         msg.symbol = str::repeat("a", 3);
-        assert!(msg.has_valid_symbol());
+        assert!(is_valid_symbol(&msg.symbol));
 
         // Too long
         msg.symbol = str::repeat("a", 13);
-        assert!(!msg.has_valid_symbol());
+        assert!(!is_valid_symbol(&msg.symbol));
 
         // Has illegal char
         let illegal_chars = [[64u8], [91u8], [123u8]];
         illegal_chars.iter().for_each(|c| {
             let c = std::str::from_utf8(c).unwrap();
             msg.symbol = str::repeat(c, 3);
-            assert!(!msg.has_valid_symbol());
+            assert!(!is_valid_symbol(&msg.symbol));
         });
     }
 }
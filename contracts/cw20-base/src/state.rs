@@ -1,8 +1,12 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cw_storage_plus::{
+    Index, IndexList, IndexedSnapshotMap, Item, Map, MultiIndex, SnapshotItem, Strategy,
+};
 
-use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw20::{
+    AllowanceResponse, ComplianceMode, Logo, MarketingInfoResponse, MinterQuota, VestingSchedule,
+};
 
 #[cw_serde]
 pub struct TokenInfo {
@@ -26,11 +30,99 @@ impl TokenInfo {
     }
 }
 
-pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+/// A minter registered in addition to the primary minter of `TokenInfo`
+#[cw_serde]
+pub struct MinterState {
+    /// cap is how much this minter may mint in total
+    pub cap: Option<Uint128>,
+    pub minted: Uint128,
+    pub quota: Option<MinterQuota>,
+    /// how much of the quota was in use at `quota_updated`
+    pub quota_used: Uint128,
+    pub quota_updated: Timestamp,
+}
+
+#[cw_serde]
+pub struct PauseInfo {
+    /// pauser is the only address allowed to pause and unpause the token
+    pub pauser: Addr,
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct ComplianceInfo {
+    /// admin is the only address allowed to update the blocklist and allowlist
+    pub admin: Addr,
+    pub mode: ComplianceMode,
+}
+
+#[cw_serde]
+pub struct FeeConfig {
+    /// rate is the share of every transfer credited to the recipient, always lower than 1
+    pub rate: Decimal,
+    pub recipient: Addr,
+    /// transfers from or to any of these addresses are not charged
+    pub exempt: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct Vesting {
+    /// amount is the part of the initial balance locked under the schedule
+    pub amount: Uint128,
+    pub schedule: VestingSchedule,
+}
+
+// TOKEN_INFO and balances() keep their original primary keys, so raw queries against
+// them keep working. The changelogs let us answer queries at a past height.
+pub const TOKEN_INFO: SnapshotItem<TokenInfo> = SnapshotItem::new(
+    "token_info",
+    "token_info__checkpoints",
+    "token_info__changelog",
+    Strategy::EveryBlock,
+);
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
-pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
+pub const MINT_ADMIN: Item<Addr> = Item::new("mint_admin");
+pub const MINTERS: Map<&Addr, MinterState> = Map::new("minters");
+pub const PAUSE_INFO: Item<PauseInfo> = Item::new("pause_info");
+pub const COMPLIANCE_INFO: Item<ComplianceInfo> = Item::new("compliance_info");
+pub const BLOCKLIST: Map<&Addr, ()> = Map::new("blocklist");
+pub const ALLOWLIST: Map<&Addr, ()> = Map::new("allowlist");
+pub const FEE_ADMIN: Item<Addr> = Item::new("fee_admin");
+pub const FEE_CONFIG: Item<FeeConfig> = Item::new("fee_config");
+pub const TOKEN_ADMIN: Item<Addr> = Item::new("token_admin");
+pub const CLAWBACK_ADMIN: Item<Addr> = Item::new("clawback_admin");
+pub const MAX_MEMO_LENGTH: Item<u32> = Item::new("max_memo_length");
+pub const VESTING: Map<&Addr, Vesting> = Map::new("vesting");
+
+pub struct BalanceIndexes<'a> {
+    /// Orders the holders by balance
+    pub amount: MultiIndex<'a, u128, Uint128, Addr>,
+}
+
+impl<'a> IndexList<Uint128> for BalanceIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Uint128>> + '_> {
+        let v: Vec<&dyn Index<Uint128>> = vec![&self.amount];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn balances<'a>() -> IndexedSnapshotMap<'a, &'a Addr, Uint128, BalanceIndexes<'a>> {
+    let indexes = BalanceIndexes {
+        amount: MultiIndex::new(|_, balance| balance.u128(), "balance", "balance__amount"),
+    };
+    IndexedSnapshotMap::new(
+        "balance",
+        "balance__checkpoints",
+        "balance__changelog",
+        Strategy::EveryBlock,
+        indexes,
+    )
+}
+
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
+/// The nonce the next permit of an owner must use
+pub const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
 pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> =
     Map::new("allowance_spender");
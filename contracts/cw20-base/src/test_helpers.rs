@@ -0,0 +1,43 @@
+#![cfg(test)]
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Deps, DepsMut, Env, Timestamp, Uint128};
+use cw20::Cw20Coin;
+
+use crate::contract::{instantiate, query_balance};
+use crate::msg::InstantiateMsg;
+
+/// Holds the initial balance of `token_msg()`
+pub const OWNER: &str = "owner";
+
+/// A token with 1000 tokens held by `OWNER`. Tests set the fields they need on top of it,
+/// eg. `InstantiateMsg { mint, ..token_msg() }`.
+pub fn token_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        name: "Auto Gen".to_string(),
+        symbol: "AUTO".to_string(),
+        decimals: 3,
+        initial_balances: vec![Cw20Coin {
+            address: OWNER.into(),
+            amount: Uint128::new(1000),
+        }],
+        ..InstantiateMsg::default()
+    }
+}
+
+pub fn instantiate_token(deps: DepsMut, env: Env, msg: InstantiateMsg) {
+    let info = mock_info("creator", &[]);
+    instantiate(deps, env, info, msg).unwrap();
+}
+
+pub fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(seconds);
+    env
+}
+
+pub fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
+    query_balance(deps, mock_env(), address.into())
+        .unwrap()
+        .balance
+}
@@ -0,0 +1,293 @@
+use cosmwasm_std::{Addr, BlockInfo, Deps, DepsMut, Env, StdResult, Storage, Timestamp, Uint128};
+use cw20::{Cw20Coin, VestingInfoResponse};
+
+use crate::error::ContractError;
+use crate::msg::InitialVesting;
+use crate::state::{balances, Vesting, VESTING};
+
+/// Locks the initial balances listed in `vesting` under their schedules. Every address must
+/// have exactly one entry in `accounts`, which must have been validated already.
+pub fn create_vesting(
+    deps: &mut DepsMut,
+    accounts: &[Cw20Coin],
+    vesting: &[InitialVesting],
+) -> Result<(), ContractError> {
+    let mut addresses = vesting.iter().map(|v| &v.address).collect::<Vec<_>>();
+    addresses.sort();
+    addresses.dedup();
+    if addresses.len() != vesting.len() {
+        return Err(ContractError::DuplicateInitialVestingAddresses {});
+    }
+
+    for row in vesting {
+        let amount = accounts
+            .iter()
+            .find(|account| account.address == row.address)
+            .map(|account| account.amount)
+            .ok_or_else(|| ContractError::VestingWithoutBalance {
+                address: row.address.clone(),
+            })?;
+        row.schedule.validate(amount)?;
+        let address = deps.api.addr_validate(&row.address)?;
+        let data = Vesting {
+            amount,
+            schedule: row.schedule.clone(),
+        };
+        VESTING.save(deps.storage, &address, &data)?;
+    }
+    Ok(())
+}
+
+/// Returns how much of the balance of `address` has not vested yet at `now`.
+pub fn locked_balance(storage: &dyn Storage, address: &Addr, now: Timestamp) -> StdResult<Uint128> {
+    let locked = VESTING
+        .may_load(storage, address)?
+        .map(|v| v.schedule.locked(v.amount, now))
+        .unwrap_or_default();
+    Ok(locked)
+}
+
+/// Returns the part of `balance` that `address` can move at `now`, or None if the address
+/// has no vesting schedule.
+pub fn spendable_balance(
+    storage: &dyn Storage,
+    address: &Addr,
+    balance: Uint128,
+    now: Timestamp,
+) -> StdResult<Option<Uint128>> {
+    let spendable = VESTING
+        .may_load(storage, address)?
+        .map(|v| balance.saturating_sub(v.schedule.locked(v.amount, now)));
+    Ok(spendable)
+}
+
+/// Returns `ContractError::LockedTokens` if debiting `amount` from `address` would dip into
+/// tokens that have not vested yet. Every handler debiting a balance must call this first.
+pub fn ensure_spendable(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    address: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let locked = locked_balance(storage, address, block.time)?;
+    if locked.is_zero() {
+        return Ok(());
+    }
+    let balance = balances().may_load(storage, address)?.unwrap_or_default();
+    // if the balance is too low anyway, let the debit fail with an overflow as usual
+    if amount <= balance && balance - amount < locked {
+        return Err(ContractError::LockedTokens {});
+    }
+    Ok(())
+}
+
+pub fn query_vesting_info(deps: Deps, env: Env, address: String) -> StdResult<VestingInfoResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let info = match VESTING.may_load(deps.storage, &address)? {
+        Some(vesting) => VestingInfoResponse {
+            amount: vesting.amount,
+            locked: vesting.schedule.locked(vesting.amount, env.block.time),
+            schedule: Some(vesting.schedule),
+        },
+        None => VestingInfoResponse::default(),
+    };
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{to_binary, StdError};
+    use cw20::{MinterResponse, VestingSchedule};
+
+    use crate::contract::{execute, instantiate, query_balance};
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::test_helpers::{env_at, instantiate_token, token_msg, OWNER};
+
+    const MINTER: &str = "minter";
+
+    fn do_instantiate(deps: DepsMut, vesting: VestingSchedule) {
+        let msg = InstantiateMsg {
+            initial_vesting: Some(vec![InitialVesting {
+                address: OWNER.into(),
+                schedule: vesting,
+            }]),
+            mint: Some(MinterResponse {
+                minter: MINTER.into(),
+                cap: None,
+            }),
+            ..token_msg()
+        };
+        instantiate_token(deps, env_at(0), msg);
+    }
+
+    fn transfer(deps: DepsMut, env: Env, amount: u128) -> Result<(), ContractError> {
+        let msg = ExecuteMsg::Transfer {
+            recipient: "rcpt".into(),
+            amount: Uint128::new(amount),
+            memo: None,
+        };
+        execute(deps, env, mock_info(OWNER, &[]), msg).map(|_| ())
+    }
+
+    fn instantiate_with(vesting: Vec<InitialVesting>) -> Result<(), ContractError> {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            initial_vesting: Some(vesting),
+            ..token_msg()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).map(|_| ())
+    }
+
+    #[test]
+    fn rejects_invalid_vesting() {
+        let cliff = VestingSchedule::Cliff {
+            time: Timestamp::from_seconds(100),
+        };
+        let vesting = |address: &str, schedule: &VestingSchedule| InitialVesting {
+            address: address.into(),
+            schedule: schedule.clone(),
+        };
+
+        let backwards = VestingSchedule::Linear {
+            start: Timestamp::from_seconds(200),
+            end: Timestamp::from_seconds(100),
+        };
+        let err = instantiate_with(vec![vesting(OWNER, &backwards)]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::generic_err(
+                "Linear vesting must end after it starts"
+            ))
+        );
+
+        let err = instantiate_with(vec![vesting("other", &cliff)]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::VestingWithoutBalance {
+                address: "other".into()
+            }
+        );
+
+        let err =
+            instantiate_with(vec![vesting(OWNER, &cliff), vesting(OWNER, &cliff)]).unwrap_err();
+        assert_eq!(err, ContractError::DuplicateInitialVestingAddresses {});
+
+        instantiate_with(vec![vesting(OWNER, &cliff)]).unwrap();
+    }
+
+    #[test]
+    fn spendable_only_set_for_vesting_accounts() {
+        let mut deps = mock_dependencies();
+        let schedule = VestingSchedule::Cliff {
+            time: Timestamp::from_seconds(100),
+        };
+        do_instantiate(deps.as_mut(), schedule);
+        transfer(deps.as_mut(), env_at(100), 10).unwrap();
+
+        let balance = query_balance(deps.as_ref(), env_at(100), OWNER.into()).unwrap();
+        assert_eq!(balance.spendable, Some(Uint128::new(990)));
+
+        // other accounts get the plain cw20 response
+        let balance = query_balance(deps.as_ref(), env_at(100), "rcpt".into()).unwrap();
+        assert_eq!(balance.spendable, None);
+        assert_eq!(
+            to_binary(&balance).unwrap().as_slice(),
+            br#"{"balance":"10"}"#
+        );
+    }
+
+    #[test]
+    fn cliff_locks_until_vested() {
+        let mut deps = mock_dependencies();
+        let schedule = VestingSchedule::Cliff {
+            time: Timestamp::from_seconds(100),
+        };
+        do_instantiate(deps.as_mut(), schedule.clone());
+
+        let balance = query_balance(deps.as_ref(), env_at(50), OWNER.into()).unwrap();
+        assert_eq!(balance.balance, Uint128::new(1000));
+        assert_eq!(balance.spendable, Some(Uint128::zero()));
+        assert_eq!(
+            query_vesting_info(deps.as_ref(), env_at(50), OWNER.into()).unwrap(),
+            VestingInfoResponse {
+                amount: Uint128::new(1000),
+                locked: Uint128::new(1000),
+                schedule: Some(schedule),
+            }
+        );
+
+        // locked tokens can neither be transferred nor burned
+        let err = transfer(deps.as_mut(), env_at(50), 1).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), env_at(50), mock_info(OWNER, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+
+        // nor moved through an allowance
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".into(),
+            amount: Uint128::new(10),
+            expires: None,
+        };
+        execute(deps.as_mut(), env_at(50), mock_info(OWNER, &[]), msg).unwrap();
+        let msg = ExecuteMsg::TransferFrom {
+            owner: OWNER.into(),
+            recipient: "rcpt".into(),
+            amount: Uint128::new(1),
+            memo: None,
+        };
+        let err = execute(deps.as_mut(), env_at(50), mock_info("spender", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+
+        // tokens received on top of the locked ones are spendable
+        let msg = ExecuteMsg::Mint {
+            recipient: OWNER.into(),
+            amount: Uint128::new(20),
+        };
+        execute(deps.as_mut(), env_at(50), mock_info(MINTER, &[]), msg).unwrap();
+        transfer(deps.as_mut(), env_at(50), 15).unwrap();
+        let err = transfer(deps.as_mut(), env_at(50), 6).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+
+        // everything is spendable after the cliff
+        let balance = query_balance(deps.as_ref(), env_at(100), OWNER.into()).unwrap();
+        assert_eq!(balance.spendable, Some(Uint128::new(1005)));
+        transfer(deps.as_mut(), env_at(100), 1005).unwrap();
+    }
+
+    #[test]
+    fn linear_unlocks_gradually() {
+        let mut deps = mock_dependencies();
+        do_instantiate(
+            deps.as_mut(),
+            VestingSchedule::Linear {
+                start: Timestamp::from_seconds(100),
+                end: Timestamp::from_seconds(200),
+            },
+        );
+
+        // a quarter has vested
+        let balance = query_balance(deps.as_ref(), env_at(125), OWNER.into()).unwrap();
+        assert_eq!(balance.spendable, Some(Uint128::new(250)));
+        let err = transfer(deps.as_mut(), env_at(125), 251).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+        transfer(deps.as_mut(), env_at(125), 250).unwrap();
+
+        // half has vested, a quarter was already spent
+        let err = transfer(deps.as_mut(), env_at(150), 251).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+        transfer(deps.as_mut(), env_at(150), 250).unwrap();
+        assert_eq!(
+            query_balance(deps.as_ref(), env_at(150), OWNER.into())
+                .unwrap()
+                .balance,
+            Uint128::new(500)
+        );
+    }
+}
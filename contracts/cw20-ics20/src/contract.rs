@@ -1,13 +1,15 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, IbcMsg, IbcQuery, MessageInfo, Order,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, IbcMsg, IbcQuery, MessageInfo, Order,
     PortIdResponse, Response, StdError, StdResult,
 };
 use semver::Version;
 
-use cw2::{get_contract_version, set_contract_version};
-use cw20::{Cw20Coin, Cw20ReceiveMsg};
+use cw2::{
+    get_contract_version, set_contract_version, set_supported_interfaces, ContractInterface,
+};
+use cw20::{AcceptedTokens, Cw20Coin, Cw20ReceiveMsg, ReceivedCw20};
 use cw_storage_plus::Bound;
 
 use crate::amount::Amount;
@@ -28,6 +30,14 @@ use cw_utils::{maybe_addr, nonpayable, one_coin};
 const CONTRACT_NAME: &str = "crates.io:cw20-ics20";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw20::interfaces::RECEIVER,
+        cw20::interfaces::VERSION,
+    )]
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -36,6 +46,7 @@ pub fn instantiate(
     msg: InitMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     let cfg = Config {
         default_timeout: msg.default_timeout,
         default_gas_limit: msg.default_gas_limit,
@@ -85,13 +96,14 @@ pub fn execute_receive(
 ) -> Result<Response, ContractError> {
     nonpayable(&info)?;
 
-    let msg: TransferMsg = from_binary(&wrapper.msg)?;
+    // execute_transfer checks the token against the allow list
+    let received: ReceivedCw20<TransferMsg> =
+        wrapper.into_checked(deps.api, &info, AcceptedTokens::Any)?;
     let amount = Amount::Cw20(Cw20Coin {
-        address: info.sender.to_string(),
-        amount: wrapper.amount,
+        address: received.token.into(),
+        amount: received.amount,
     });
-    let api = deps.api;
-    execute_transfer(deps, env, msg, amount, api.addr_validate(&wrapper.sender)?)
+    execute_transfer(deps, env, received.msg, amount, received.sender)
 }
 
 pub fn execute_transfer(
@@ -157,7 +169,7 @@ pub fn execute_transfer(
         .add_attribute("sender", &packet.sender)
         .add_attribute("receiver", &packet.receiver)
         .add_attribute("denom", &packet.denom)
-        .add_attribute("amount", &packet.amount.to_string());
+        .add_attribute("amount", packet.amount.to_string());
     Ok(res)
 }
 
@@ -262,6 +274,7 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response,
     if storage_version < version {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
 
     Ok(Response::new())
 }
@@ -383,7 +396,7 @@ mod test {
     use crate::test_helpers::*;
 
     use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
-    use cosmwasm_std::{coin, coins, CosmosMsg, IbcMsg, StdError, Uint128};
+    use cosmwasm_std::{coin, coins, from_binary, CosmosMsg, IbcMsg, StdError, Uint128};
 
     use crate::state::ChannelState;
     use cw_utils::PaymentError;
@@ -497,6 +510,7 @@ mod test {
             sender: "my-account".into(),
             amount: Uint128::new(888777666),
             msg: to_binary(&transfer).unwrap(),
+            memo: None,
         });
 
         // works with proper funds
@@ -543,6 +557,7 @@ mod test {
             sender: "my-account".into(),
             amount: Uint128::new(888777666),
             msg: to_binary(&transfer).unwrap(),
+            memo: None,
         });
 
         // rejected as not on allow list
@@ -374,6 +374,7 @@ fn send_amount(amount: Amount, recipient: String) -> CosmosMsg {
             let msg = Cw20ExecuteMsg::Transfer {
                 recipient,
                 amount: coin.amount,
+                memo: None,
             };
             WasmMsg::Execute {
                 contract_addr: coin.address,
@@ -432,6 +433,7 @@ mod test {
         let msg = Cw20ExecuteMsg::Transfer {
             recipient: recipient.into(),
             amount: Uint128::new(amount),
+            memo: None,
         };
         let exec = WasmMsg::Execute {
             contract_addr: address.into(),
@@ -516,6 +518,7 @@ mod test {
             sender: "local-sender".to_string(),
             amount: Uint128::new(987654321),
             msg: to_binary(&transfer).unwrap(),
+            memo: None,
         });
         let info = mock_info(cw20_addr, &[]);
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
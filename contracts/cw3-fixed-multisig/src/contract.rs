@@ -7,7 +7,7 @@ use cosmwasm_std::{
     Response, StdResult,
 };
 
-use cw2::set_contract_version;
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
 use cw3::{
     Ballot, Proposal, ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo,
     VoteListResponse, VoteResponse, VoterDetail, VoterListResponse, VoterResponse, Votes,
@@ -23,6 +23,14 @@ use crate::state::{next_id, Config, BALLOTS, CONFIG, PROPOSALS, VOTERS};
 const CONTRACT_NAME: &str = "crates.io:cw3-fixed-multisig";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw3::INTERFACE,
+        cw3::INTERFACE_VERSION,
+    )]
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -38,6 +46,7 @@ pub fn instantiate(
     msg.threshold.validate(total_weight)?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
 
     let cfg = Config {
         threshold: msg.threshold,
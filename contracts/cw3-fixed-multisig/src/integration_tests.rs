@@ -82,6 +82,7 @@ fn cw3_controls_cw20() {
             cap: None,
         }),
         marketing: None,
+        ..Default::default()
     };
     let cw20_addr = router
         .instantiate_contract(
@@ -7,7 +7,7 @@ use cosmwasm_std::{
     Response, StdResult,
 };
 
-use cw2::set_contract_version;
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
 
 use cw3::{
     Ballot, Proposal, ProposalListResponse, ProposalResponse, Status, Vote, VoteInfo,
@@ -26,6 +26,14 @@ use crate::state::{Config, CONFIG};
 const CONTRACT_NAME: &str = "crates.io:cw3-flex-multisig";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw3::INTERFACE,
+        cw3::INTERFACE_VERSION,
+    )]
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -47,6 +55,7 @@ pub fn instantiate(
         .transpose()?;
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
 
     let cfg = Config {
         threshold: msg.threshold,
@@ -2186,6 +2195,7 @@ mod tests {
                     ],
                     mint: None,
                     marketing: None,
+                    ..Default::default()
                 },
                 &[],
                 "Token",
@@ -4,7 +4,7 @@ use cosmwasm_std::{
     attr, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult,
     SubMsg, Uint64,
 };
-use cw2::set_contract_version;
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
 use cw4::{
     Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
     TotalWeightResponse,
@@ -21,6 +21,14 @@ use crate::state::{ADMIN, HOOKS, MEMBERS, TOTAL};
 const CONTRACT_NAME: &str = "crates.io:cw4-group";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![ContractInterface::new(
+        cw4::INTERFACE,
+        cw4::INTERFACE_VERSION,
+    )]
+}
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -31,6 +39,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     create(deps, msg.admin, msg.members, env.block.height)?;
     Ok(Response::default())
 }
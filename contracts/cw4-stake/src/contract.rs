@@ -1,12 +1,12 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, from_slice, to_binary, Addr, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Order,
-    Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+    SubMsg, Uint128,
 };
 
-use cw2::set_contract_version;
-use cw20::{Balance, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw2::{set_contract_version, set_supported_interfaces, ContractInterface};
+use cw20::{AcceptedTokens, Asset, Balance, Cw20CoinVerified, Cw20ReceiveMsg, Denom, ReceivedCw20};
 use cw4::{
     Member, MemberChangedHookMsg, MemberDiff, MemberListResponse, MemberResponse,
     TotalWeightResponse,
@@ -22,6 +22,14 @@ use crate::state::{Config, ADMIN, CLAIMS, CONFIG, HOOKS, MEMBERS, STAKE, TOTAL};
 const CONTRACT_NAME: &str = "crates.io:cw4-stake";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// the specs this contract implements, advertised in cw2::SUPPORTED_INTERFACES
+fn supported_interfaces() -> Vec<ContractInterface> {
+    vec![
+        ContractInterface::new(cw4::INTERFACE, cw4::INTERFACE_VERSION),
+        ContractInterface::new(cw20::interfaces::RECEIVER, cw20::interfaces::VERSION),
+    ]
+}
+
 // Note, you can use StdResult in some functions where you do not
 // make use of the custom errors
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -32,6 +40,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    set_supported_interfaces(deps.storage, supported_interfaces())?;
     let api = deps.api;
     ADMIN.set(deps.branch(), maybe_addr(api, msg.admin)?)?;
 
@@ -127,20 +136,15 @@ pub fn execute_receive(
     info: MessageInfo,
     wrapper: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
-    // info.sender is the address of the cw20 contract (that re-sent this message).
-    // wrapper.sender is the address of the user that requested the cw20 contract to send this.
-    // This cannot be fully trusted (the cw20 contract can fake it), so only use it for actions
-    // in the address's favor (like paying/bonding tokens, not withdrawls)
-    let msg: ReceiveMsg = from_slice(&wrapper.msg)?;
+    let cfg = CONFIG.load(deps.storage)?;
+    let received: ReceivedCw20<ReceiveMsg> =
+        wrapper.into_checked(deps.api, &info, AcceptedTokens::Denom(&cfg.denom))?;
     let balance = Balance::Cw20(Cw20CoinVerified {
-        address: info.sender,
-        amount: wrapper.amount,
+        address: received.token,
+        amount: received.amount,
     });
-    let api = deps.api;
-    match msg {
-        ReceiveMsg::Bond {} => {
-            execute_bond(deps, env, balance, api.addr_validate(&wrapper.sender)?)
-        }
+    match received.msg {
+        ReceiveMsg::Bond {} => execute_bond(deps, env, balance, received.sender),
     }
 }
 
@@ -250,30 +254,11 @@ pub fn execute_claim(
     }
 
     let config = CONFIG.load(deps.storage)?;
-    let (amount_str, message) = match &config.denom {
-        Denom::Native(denom) => {
-            let amount_str = coin_to_string(release, denom.as_str());
-            let amount = coins(release.u128(), denom);
-            let message = SubMsg::new(BankMsg::Send {
-                to_address: info.sender.to_string(),
-                amount,
-            });
-            (amount_str, message)
-        }
-        Denom::Cw20(addr) => {
-            let amount_str = coin_to_string(release, addr.as_str());
-            let transfer = Cw20ExecuteMsg::Transfer {
-                recipient: info.sender.clone().into(),
-                amount: release,
-            };
-            let message = SubMsg::new(WasmMsg::Execute {
-                contract_addr: addr.into(),
-                msg: to_binary(&transfer)?,
-                funds: vec![],
-            });
-            (amount_str, message)
-        }
+    let amount_str = match &config.denom {
+        Denom::Native(denom) => coin_to_string(release, denom.as_str()),
+        Denom::Cw20(addr) => coin_to_string(release, addr.as_str()),
     };
+    let message = SubMsg::new(Asset::new(config.denom, release).transfer_msg(&info.sender)?);
 
     Ok(Response::new()
         .add_submessage(message)
@@ -359,9 +344,10 @@ fn list_members(
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{
-        coin, from_slice, CosmosMsg, OverflowError, OverflowOperation, StdError, Storage,
+        coin, coins, from_slice, BankMsg, CosmosMsg, OverflowError, OverflowOperation, StdError,
+        Storage, WasmMsg,
     };
-    use cw20::Denom;
+    use cw20::{Cw20ExecuteMsg, Denom};
     use cw4::{member_key, TOTAL_KEY};
     use cw_controllers::{AdminError, Claim, HookError};
     use cw_utils::Duration;
@@ -441,6 +427,7 @@ mod tests {
                     sender: addr.to_string(),
                     amount: Uint128::new(*stake),
                     msg: to_binary(&ReceiveMsg::Bond {}).unwrap(),
+                    memo: None,
                 });
                 let info = mock_info(CW20_ADDRESS, &[]);
                 execute(deps.branch(), env.clone(), info, msg).unwrap();
@@ -502,7 +489,7 @@ mod tests {
         // this is only valid if we are not doing a historical query
         if height.is_none() {
             // compute expected metrics
-            let weights = vec![user1_weight, user2_weight, user3_weight];
+            let weights = [user1_weight, user2_weight, user3_weight];
             let sum: u64 = weights.iter().map(|x| x.unwrap_or_default()).sum();
             let count = weights.iter().filter(|x| x.is_some()).count();
 
@@ -621,6 +608,23 @@ mod tests {
         // Assert updated weights
         assert_stake(deps.as_ref(), 12_000, 7_500, 4_000);
         assert_users(deps.as_ref(), Some(12), Some(7), None, None);
+
+        // other tokens are rejected before the message is handled
+        let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: USER1.into(),
+            amount: Uint128::new(5_000),
+            msg: to_binary(&ReceiveMsg::Bond {}).unwrap(),
+            memo: None,
+        });
+        let err =
+            execute(deps.as_mut(), mock_env(), mock_info("fake-token", &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::generic_err(
+                "Tokens of fake-token are not accepted"
+            ))
+        );
+        assert_stake(deps.as_ref(), 12_000, 7_500, 4_000);
     }
 
     #[test]
@@ -674,7 +678,8 @@ mod tests {
                     parsed,
                     Cw20ExecuteMsg::Transfer {
                         recipient: USER1.into(),
-                        amount: Uint128::new(7_900)
+                        amount: Uint128::new(7_900),
+                        memo: None,
                     }
                 );
             }
@@ -931,7 +936,7 @@ mod tests {
         let add_msg2 = ExecuteMsg::AddHook {
             addr: contract2.clone(),
         };
-        for msg in vec![add_msg, add_msg2] {
+        for msg in [add_msg, add_msg2] {
             let _ = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
         }
 
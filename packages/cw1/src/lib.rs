@@ -28,3 +28,8 @@ pub mod query;
 pub use crate::helpers::Cw1Contract;
 pub use crate::msg::Cw1ExecuteMsg;
 pub use crate::query::{CanExecuteResponse, Cw1QueryMsg};
+
+/// The name under which contracts advertise this spec in `cw2::SUPPORTED_INTERFACES`
+pub const INTERFACE: &str = "cw1";
+/// The version of this spec, advertised along with `INTERFACE`
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
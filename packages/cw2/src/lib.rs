@@ -13,16 +13,24 @@ Since the state is well defined, we do not need to support any "smart queries".
 We do provide a helper to construct a "raw query" to read the ContractInfo
 of any CW2-compliant contract.
 
+Contracts should also store the list of spec interfaces they implement under the
+`"supported_interfaces"` key, so others can check for an extension with a raw query
+instead of probing it with smart queries.
+
 For more information on this specification, please check out the
 [README](https://github.com/CosmWasm/cw-plus/blob/main/packages/cw2/README.md).
 */
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{CustomQuery, QuerierWrapper, QueryRequest, StdResult, Storage, WasmQuery};
+use cosmwasm_std::{
+    from_slice, CustomQuery, QuerierWrapper, QueryRequest, StdResult, Storage, WasmQuery,
+};
 use cw_storage_plus::Item;
 
 pub const CONTRACT: Item<ContractVersion> = Item::new("contract_info");
 
+pub const SUPPORTED_INTERFACES: Item<Vec<ContractInterface>> = Item::new("supported_interfaces");
+
 #[cw_serde]
 pub struct ContractVersion {
     /// contract is the crate name of the implementing contract, eg. `crate:cw20-base`
@@ -35,6 +43,23 @@ pub struct ContractVersion {
     pub version: String,
 }
 
+#[cw_serde]
+pub struct ContractInterface {
+    /// interface is the name of a spec, or of an extension of one, eg. `cw20` or `cw20/allowance`
+    pub interface: String,
+    /// version is the version of the spec that is implemented, eg. `1.0.0`
+    pub version: String,
+}
+
+impl ContractInterface {
+    pub fn new(interface: impl Into<String>, version: impl Into<String>) -> Self {
+        ContractInterface {
+            interface: interface.into(),
+            version: version.into(),
+        }
+    }
+}
+
 /// get_contract_version can be use in migrate to read the previous version of this contract
 pub fn get_contract_version(store: &dyn Storage) -> StdResult<ContractVersion> {
     CONTRACT.load(store)
@@ -74,10 +99,43 @@ where
     querier.query(&req)
 }
 
+/// set_supported_interfaces should be used in instantiate and after a successful migrate to store
+/// every interface the contract implements. It replaces the previously stored list.
+pub fn set_supported_interfaces(
+    store: &mut dyn Storage,
+    interfaces: Vec<ContractInterface>,
+) -> StdResult<()> {
+    SUPPORTED_INTERFACES.save(store, &interfaces)
+}
+
+/// get_supported_interfaces returns the interfaces this contract advertises, which is empty
+/// if they were never set
+pub fn get_supported_interfaces(store: &dyn Storage) -> StdResult<Vec<ContractInterface>> {
+    Ok(SUPPORTED_INTERFACES.may_load(store)?.unwrap_or_default())
+}
+
+/// This will make a raw_query to another contract to get the interfaces it advertises.
+/// A contract that does not advertise any returns an empty list, in which case nothing
+/// can be concluded about what it supports.
+pub fn query_supported_interfaces<T, CQ>(
+    querier: &QuerierWrapper<CQ>,
+    contract_addr: T,
+) -> StdResult<Vec<ContractInterface>>
+where
+    T: Into<String>,
+    CQ: CustomQuery,
+{
+    match querier.query_wasm_raw(contract_addr, SUPPORTED_INTERFACES.as_slice())? {
+        Some(data) => from_slice(&data),
+        None => Ok(vec![]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::testing::{MockQuerier, MockStorage};
+    use cosmwasm_std::{to_binary, Binary, ContractResult, Empty, SystemResult};
 
     #[test]
     fn get_and_set_work() {
@@ -98,4 +156,49 @@ mod tests {
         };
         assert_eq!(expected, loaded);
     }
+
+    #[test]
+    fn supported_interfaces_work() {
+        let mut store = MockStorage::new();
+        assert_eq!(get_supported_interfaces(&store).unwrap(), vec![]);
+
+        let interfaces = vec![
+            ContractInterface::new("cw20", "1.0.0"),
+            ContractInterface::new("cw20/allowance", "1.0.0"),
+        ];
+        set_supported_interfaces(&mut store, interfaces.clone()).unwrap();
+        assert_eq!(get_supported_interfaces(&store).unwrap(), interfaces);
+
+        // a later call replaces the list
+        let interfaces = vec![ContractInterface::new("cw20", "1.1.0")];
+        set_supported_interfaces(&mut store, interfaces.clone()).unwrap();
+        assert_eq!(get_supported_interfaces(&store).unwrap(), interfaces);
+    }
+
+    #[test]
+    fn query_supported_interfaces_works() {
+        let mut querier = MockQuerier::<Empty>::new(&[]);
+        querier.update_wasm(|query| match query {
+            WasmQuery::Raw { contract_addr, key } => {
+                assert_eq!(key.as_slice(), b"supported_interfaces");
+                let data = match contract_addr.as_str() {
+                    "token" => to_binary(&[ContractInterface::new("cw20", "1.0.0")]).unwrap(),
+                    // raw queries of a missing key return no data
+                    _ => Binary::default(),
+                };
+                SystemResult::Ok(ContractResult::Ok(data))
+            }
+            _ => panic!("unexpected query"),
+        });
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        assert_eq!(
+            query_supported_interfaces(&querier, "token").unwrap(),
+            vec![ContractInterface::new("cw20", "1.0.0")]
+        );
+        assert_eq!(
+            query_supported_interfaces(&querier, "legacy").unwrap(),
+            vec![]
+        );
+    }
 }
@@ -0,0 +1,132 @@
+/*!
+Conversion between amounts in base units and decimal strings, using the `decimals` of the
+token (see `TokenInfoResponse`). With 6 decimals, `12345000` base units are `"12.345000"`.
+
+Both directions work on the digits directly, so there is no rounding: `format` always prints
+exactly `decimals` fraction digits, and `parse` rejects strings that are more precise than the
+token.
+*/
+
+use cosmwasm_std::{StdError, StdResult, Uint128};
+
+/// Formats base units as a decimal string with exactly `decimals` fraction digits
+pub fn format(amount: Uint128, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}.{}", whole, fraction)
+}
+
+/// Parses a decimal string, such as `"12.345"`, into base units. Fraction digits beyond
+/// `decimals` are only accepted if they are zeros. Signs, exponents and separators are
+/// rejected.
+pub fn parse(amount: &str, decimals: u8) -> StdResult<Uint128> {
+    let invalid = |reason: &str| StdError::parse_err("amount", format!("{}: {}", reason, amount));
+
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((_, "")) => {
+            return Err(invalid("missing digits after the decimal point"));
+        }
+        Some((whole, fraction)) => (whole, fraction),
+        None => (amount, ""),
+    };
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(invalid("not a decimal number"));
+    }
+
+    let decimals = decimals as usize;
+    let fraction = if fraction.len() > decimals {
+        let (kept, dropped) = fraction.split_at(decimals);
+        if dropped.chars().any(|c| c != '0') {
+            return Err(invalid(&format!("more than {} decimal places", decimals)));
+        }
+        kept
+    } else {
+        fraction
+    };
+    let digits = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(Uint128::zero());
+    }
+    digits
+        .parse::<u128>()
+        .map(Uint128::new)
+        .map_err(|_| invalid("too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_works() {
+        assert_eq!(format(Uint128::new(12_345_000), 6), "12.345000");
+        assert_eq!(format(Uint128::new(5), 6), "0.000005");
+        assert_eq!(format(Uint128::new(1_000_000), 6), "1.000000");
+        assert_eq!(format(Uint128::zero(), 3), "0.000");
+        assert_eq!(format(Uint128::new(42), 0), "42");
+        assert_eq!(
+            format(Uint128::MAX, 18),
+            "340282366920938463463.374607431768211455"
+        );
+        assert_eq!(
+            format(Uint128::new(7), 40),
+            "0.0000000000000000000000000000000000000007"
+        );
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(parse("12.345000", 6).unwrap(), Uint128::new(12_345_000));
+        assert_eq!(parse("12.345", 6).unwrap(), Uint128::new(12_345_000));
+        assert_eq!(parse("12", 6).unwrap(), Uint128::new(12_000_000));
+        assert_eq!(parse("0.000005", 6).unwrap(), Uint128::new(5));
+        assert_eq!(parse("007.5", 1).unwrap(), Uint128::new(75));
+        assert_eq!(parse("0", 6).unwrap(), Uint128::zero());
+        assert_eq!(parse("42", 0).unwrap(), Uint128::new(42));
+        // zeros beyond the precision of the token lose nothing
+        assert_eq!(parse("1.50000000", 2).unwrap(), Uint128::new(150));
+
+        // round trips
+        for (amount, decimals) in [
+            (Uint128::MAX, 18),
+            (Uint128::new(1), 6),
+            (Uint128::zero(), 0),
+        ] {
+            assert_eq!(parse(&format(amount, decimals), decimals).unwrap(), amount);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_amounts() {
+        for invalid in [
+            "", ".", "1.", ".5", "-1", "+1", "1,000", "1_000", "1e6", " 1", "1.2.3", "0x10", "½",
+        ] {
+            parse(invalid, 6).unwrap_err();
+        }
+
+        let err = parse("1.0000001", 6).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::parse_err("amount", "more than 6 decimal places: 1.0000001")
+        );
+        parse("0.5", 0).unwrap_err();
+
+        // one above the maximum
+        let err = parse("340282366920938463463.374607431768211456", 18).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::parse_err(
+                "amount",
+                "too large: 340282366920938463463.374607431768211456"
+            )
+        );
+        parse("1", 39).unwrap_err();
+    }
+}
@@ -0,0 +1,338 @@
+use std::fmt;
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, CustomQuery, Deps, QuerierWrapper, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+
+use crate::{Balance, Cw20CoinVerified, Cw20ExecuteMsg, Denom, UncheckedDenom};
+
+/// An amount of either native or cw20 tokens
+#[cw_serde]
+pub struct Asset {
+    pub info: Denom,
+    pub amount: Uint128,
+}
+
+/// An amount of either native or cw20 tokens, as received in messages.
+/// To validate, transform into `Asset` via `into_checked()`.
+#[cw_serde]
+pub struct UncheckedAsset {
+    pub info: UncheckedDenom,
+    pub amount: Uint128,
+}
+
+impl UncheckedAsset {
+    /// Validates the cw20 address, and that it points to a cw20 token
+    pub fn into_checked(self, deps: Deps) -> StdResult<Asset> {
+        Ok(Asset {
+            info: self.info.into_checked(deps)?,
+            amount: self.amount,
+        })
+    }
+}
+
+impl Asset {
+    pub fn new(info: Denom, amount: impl Into<Uint128>) -> Self {
+        Asset {
+            info,
+            amount: amount.into(),
+        }
+    }
+
+    pub fn native(denom: impl Into<String>, amount: impl Into<Uint128>) -> Self {
+        Asset::new(Denom::Native(denom.into()), amount)
+    }
+
+    pub fn cw20(address: Addr, amount: impl Into<Uint128>) -> Self {
+        Asset::new(Denom::Cw20(address), amount)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    /// Creates a message sending these tokens from the calling contract to `recipient`
+    pub fn transfer_msg(&self, recipient: impl Into<String>) -> StdResult<CosmosMsg> {
+        let msg = match &self.info {
+            Denom::Native(denom) => BankMsg::Send {
+                to_address: recipient.into(),
+                amount: vec![Coin {
+                    denom: denom.clone(),
+                    amount: self.amount,
+                }],
+            }
+            .into(),
+            Denom::Cw20(address) => WasmMsg::Execute {
+                contract_addr: address.into(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.into(),
+                    amount: self.amount,
+                    memo: None,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        };
+        Ok(msg)
+    }
+
+    /// Creates a message spending the allowance the calling contract has on the tokens of
+    /// `owner`. Native tokens have no allowances, so this errors for them; they have to be
+    /// sent along with the message instead.
+    pub fn transfer_from_msg(
+        &self,
+        owner: impl Into<String>,
+        recipient: impl Into<String>,
+    ) -> StdResult<CosmosMsg> {
+        match &self.info {
+            Denom::Native(denom) => Err(StdError::generic_err(format!(
+                "Cannot transfer native {} on behalf of another account",
+                denom
+            ))),
+            Denom::Cw20(address) => Ok(WasmMsg::Execute {
+                contract_addr: address.into(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: owner.into(),
+                    recipient: recipient.into(),
+                    amount: self.amount,
+                    memo: None,
+                })?,
+                funds: vec![],
+            }
+            .into()),
+        }
+    }
+
+    /// Queries how many of these tokens `address` holds
+    pub fn query_balance<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        address: T,
+    ) -> StdResult<Uint128>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        self.info.query_balance(querier, address)
+    }
+
+    /// Adds two amounts of the same tokens
+    pub fn checked_add(&self, other: &Asset) -> StdResult<Asset> {
+        self.ensure_same_info(other, "add")?;
+        Ok(Asset::new(
+            self.info.clone(),
+            self.amount.checked_add(other.amount)?,
+        ))
+    }
+
+    /// Subtracts an amount of the same tokens
+    pub fn checked_sub(&self, other: &Asset) -> StdResult<Asset> {
+        self.ensure_same_info(other, "subtract")?;
+        Ok(Asset::new(
+            self.info.clone(),
+            self.amount.checked_sub(other.amount)?,
+        ))
+    }
+
+    fn ensure_same_info(&self, other: &Asset, operation: &str) -> StdResult<()> {
+        if self.info != other.info {
+            return Err(StdError::generic_err(format!(
+                "Cannot {} {} and {}",
+                operation, self.info, other.info
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl From<Coin> for Asset {
+    fn from(coin: Coin) -> Asset {
+        Asset::native(coin.denom, coin.amount)
+    }
+}
+
+impl From<Cw20CoinVerified> for Asset {
+    fn from(coin: Cw20CoinVerified) -> Asset {
+        Asset::cw20(coin.address, coin.amount)
+    }
+}
+
+impl From<Asset> for Balance {
+    fn from(asset: Asset) -> Balance {
+        match asset.info {
+            Denom::Native(denom) => vec![Coin {
+                denom,
+                amount: asset.amount,
+            }]
+            .into(),
+            Denom::Cw20(address) => Cw20CoinVerified {
+                address,
+                amount: asset.amount,
+            }
+            .into(),
+        }
+    }
+}
+
+impl From<Asset> for UncheckedAsset {
+    fn from(asset: Asset) -> UncheckedAsset {
+        UncheckedAsset {
+            info: asset.info.into(),
+            amount: asset.amount,
+        }
+    }
+}
+
+/// Formats as `100native:uatom` or `100cw20:<address>`
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
+/// Formats as `100native:uatom` or `100cw20:<address>`
+impl fmt::Display for UncheckedAsset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
+/// Parses `100native:uatom` or `100cw20:<address>`. The address still needs to be checked
+/// with `into_checked`.
+impl FromStr for UncheckedAsset {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> StdResult<Self> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (amount, info) = s.split_at(split);
+        if amount.is_empty() {
+            return Err(StdError::parse_err(
+                "UncheckedAsset",
+                format!("missing amount in {}", s),
+            ));
+        }
+        Ok(UncheckedAsset {
+            info: info.parse()?,
+            amount: amount.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{
+        coins, from_binary, ContractResult, Empty, OverflowError, OverflowOperation, SystemResult,
+        WasmQuery,
+    };
+
+    use crate::{BalanceResponse, Cw20QueryMsg};
+
+    #[test]
+    fn transfer_messages() {
+        let atom = Asset::native("uatom", 100u128);
+        assert_eq!(
+            atom.transfer_msg("bob").unwrap(),
+            BankMsg::Send {
+                to_address: "bob".into(),
+                amount: coins(100, "uatom"),
+            }
+            .into()
+        );
+        atom.transfer_from_msg("alice", "bob").unwrap_err();
+
+        let token = Asset::cw20(Addr::unchecked("token"), 100u128);
+        assert_eq!(
+            token.transfer_from_msg("alice", "bob").unwrap(),
+            WasmMsg::Execute {
+                contract_addr: "token".into(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "alice".into(),
+                    recipient: "bob".into(),
+                    amount: Uint128::new(100),
+                    memo: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn query_balances() {
+        let mut querier = MockQuerier::<Empty>::new(&[("alice", &coins(30, "uatom"))]);
+        querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, msg } if contract_addr == "token" => {
+                let balance = match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } if address == "alice" => 70u128,
+                    _ => 0,
+                };
+                let res = BalanceResponse {
+                    balance: Uint128::new(balance),
+                    spendable: None,
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+            }
+            _ => panic!("unexpected query"),
+        });
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        let atom = Asset::native("uatom", 1u128);
+        assert_eq!(atom.query_balance(&querier, "alice").unwrap().u128(), 30);
+        assert_eq!(atom.query_balance(&querier, "bob").unwrap().u128(), 0);
+        let token = Asset::cw20(Addr::unchecked("token"), 1u128);
+        assert_eq!(token.query_balance(&querier, "alice").unwrap().u128(), 70);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let a = Asset::native("uatom", 100u128);
+        let b = Asset::native("uatom", 30u128);
+        assert_eq!(a.checked_add(&b).unwrap(), Asset::native("uatom", 130u128));
+        assert_eq!(a.checked_sub(&b).unwrap(), Asset::native("uatom", 70u128));
+        assert_eq!(
+            b.checked_sub(&a).unwrap_err(),
+            StdError::overflow(OverflowError::new(OverflowOperation::Sub, 30u128, 100u128))
+        );
+
+        // only the same tokens can be combined
+        let other = Asset::cw20(Addr::unchecked("uatom"), 30u128);
+        let err = a.checked_add(&other).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Cannot add native:uatom and cw20:uatom")
+        );
+    }
+
+    #[test]
+    fn display_and_parse() {
+        let atom = Asset::native("uatom", 100u128);
+        assert_eq!(atom.to_string(), "100native:uatom");
+        let token = Asset::cw20(Addr::unchecked("contract1"), 5u128);
+        assert_eq!(token.to_string(), "5cw20:contract1");
+
+        let parsed: UncheckedAsset = "100native:uatom".parse().unwrap();
+        assert_eq!(parsed, atom.into());
+        let parsed: UncheckedAsset = token.to_string().parse().unwrap();
+        assert_eq!(parsed, token.into());
+        let parsed: UncheckedAsset = "7native:ibc/27394FB0".parse().unwrap();
+        assert_eq!(parsed.info, UncheckedDenom::Native("ibc/27394FB0".into()));
+
+        for invalid in [
+            "native:uatom",
+            "100",
+            "100uatom",
+            "100native:",
+            "100cw20:",
+            "-1native:a",
+        ] {
+            UncheckedAsset::from_str(invalid).unwrap_err();
+        }
+    }
+}
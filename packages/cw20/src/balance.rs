@@ -1,11 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Coin;
+use cosmwasm_std::{BankMsg, Coin, CosmosMsg, StdResult, Uint128};
 
 use std::fmt;
 
 use cw_utils::NativeBalance;
 
-use crate::Cw20CoinVerified;
+use crate::{Asset, Cw20CoinVerified, Denom};
 
 #[cw_serde]
 
@@ -58,3 +58,358 @@ impl From<Cw20CoinVerified> for Balance {
         Balance::Cw20(cw20_coin)
     }
 }
+
+/// Several native coins and cw20 tokens held at once, eg. by an escrow. The lists may be built
+/// or deserialized in any order, the methods treat duplicates as one entry and keep both lists
+/// sorted, without duplicates or zero amounts, once they change them.
+#[cw_serde]
+#[derive(Default)]
+pub struct MultiBalance {
+    pub native: Vec<Coin>,
+    pub cw20: Vec<Cw20CoinVerified>,
+}
+
+impl MultiBalance {
+    pub fn is_empty(&self) -> bool {
+        self.native.iter().all(|c| c.amount.is_zero()) && self.cw20.iter().all(|c| c.is_empty())
+    }
+
+    /// Sorts both lists, merging duplicates and removing zero amounts
+    pub fn normalize(&mut self) {
+        let mut native = NativeBalance(std::mem::take(&mut self.native));
+        native.normalize();
+        self.native = native.into_vec();
+
+        self.cw20.retain(|c| !c.is_empty());
+        self.cw20.sort_by(|a, b| a.address.cmp(&b.address));
+        self.cw20.dedup_by(|c, prev| {
+            let duplicate = c.address == prev.address;
+            if duplicate {
+                prev.amount += c.amount;
+            }
+            duplicate
+        });
+    }
+
+    /// returns how many of the given tokens are held
+    pub fn amount_of(&self, info: &Denom) -> Uint128 {
+        match info {
+            Denom::Native(denom) => self
+                .native
+                .iter()
+                .filter(|c| &c.denom == denom)
+                .map(|c| c.amount)
+                .sum(),
+            Denom::Cw20(address) => self
+                .cw20
+                .iter()
+                .filter(|c| &c.address == address)
+                .map(|c| c.amount)
+                .sum(),
+        }
+    }
+
+    /// returns true if at least the required amount is held
+    pub fn has(&self, required: &Asset) -> bool {
+        self.amount_of(&required.info) >= required.amount
+    }
+
+    pub fn add(&mut self, asset: impl Into<Asset>) -> StdResult<()> {
+        let asset = asset.into();
+        let amount = self.amount_of(&asset.info).checked_add(asset.amount)?;
+        self.set(asset.info, amount);
+        Ok(())
+    }
+
+    /// Fails without changing the balance if less than the given amount is held
+    pub fn checked_sub(&mut self, asset: impl Into<Asset>) -> StdResult<()> {
+        let asset = asset.into();
+        let amount = self.amount_of(&asset.info).checked_sub(asset.amount)?;
+        self.set(asset.info, amount);
+        Ok(())
+    }
+
+    /// Adds everything held by other. Fails without changing the balance on overflow.
+    pub fn merge(&mut self, other: MultiBalance) -> StdResult<()> {
+        let mut merged = self.clone();
+        for asset in other.assets() {
+            merged.add(asset)?;
+        }
+        *self = merged;
+        Ok(())
+    }
+
+    /// Lists every asset held, native coins first
+    pub fn assets(&self) -> impl Iterator<Item = Asset> + '_ {
+        let native = self.native.iter().cloned().map(Asset::from);
+        let cw20 = self.cw20.iter().cloned().map(Asset::from);
+        native.chain(cw20).filter(|asset| !asset.is_zero())
+    }
+
+    /// Creates the messages sending everything held to recipient: one bank send for all native
+    /// coins, and one transfer for every cw20 token
+    pub fn transfer_msgs(&self, recipient: impl Into<String>) -> StdResult<Vec<CosmosMsg>> {
+        let recipient = recipient.into();
+        let mut balance = self.clone();
+        balance.normalize();
+        let mut msgs = vec![];
+        if !balance.native.is_empty() {
+            msgs.push(
+                BankMsg::Send {
+                    to_address: recipient.clone(),
+                    amount: balance.native,
+                }
+                .into(),
+            );
+        }
+        for coin in balance.cw20 {
+            msgs.push(Asset::from(coin).transfer_msg(&recipient)?);
+        }
+        Ok(msgs)
+    }
+
+    fn set(&mut self, info: Denom, amount: Uint128) {
+        self.normalize();
+        match info {
+            Denom::Native(denom) => match self.native.iter().position(|c| c.denom >= denom) {
+                Some(i) if self.native[i].denom == denom && amount.is_zero() => {
+                    self.native.remove(i);
+                }
+                Some(i) if self.native[i].denom == denom => self.native[i].amount = amount,
+                _ if amount.is_zero() => {}
+                Some(i) => self.native.insert(i, Coin { denom, amount }),
+                None => self.native.push(Coin { denom, amount }),
+            },
+            Denom::Cw20(address) => match self.cw20.iter().position(|c| c.address >= address) {
+                Some(i) if self.cw20[i].address == address && amount.is_zero() => {
+                    self.cw20.remove(i);
+                }
+                Some(i) if self.cw20[i].address == address => self.cw20[i].amount = amount,
+                _ if amount.is_zero() => {}
+                Some(i) => self.cw20.insert(i, Cw20CoinVerified { address, amount }),
+                None => self.cw20.push(Cw20CoinVerified { address, amount }),
+            },
+        }
+    }
+}
+
+/// Formats as a comma separated list of assets, eg. `100native:uatom,5cw20:<address>`
+impl fmt::Display for MultiBalance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let assets: Vec<String> = self.assets().map(|asset| asset.to_string()).collect();
+        write!(f, "{}", assets.join(","))
+    }
+}
+
+impl From<Vec<Coin>> for MultiBalance {
+    fn from(coins: Vec<Coin>) -> MultiBalance {
+        let mut native = NativeBalance(coins);
+        native.normalize();
+        MultiBalance {
+            native: native.into_vec(),
+            cw20: vec![],
+        }
+    }
+}
+
+impl From<Cw20CoinVerified> for MultiBalance {
+    fn from(cw20_coin: Cw20CoinVerified) -> MultiBalance {
+        let cw20 = if cw20_coin.is_empty() {
+            vec![]
+        } else {
+            vec![cw20_coin]
+        };
+        MultiBalance {
+            native: vec![],
+            cw20,
+        }
+    }
+}
+
+impl From<Asset> for MultiBalance {
+    fn from(asset: Asset) -> MultiBalance {
+        let mut balance = MultiBalance::default();
+        balance.set(asset.info, asset.amount);
+        balance
+    }
+}
+
+impl From<Balance> for MultiBalance {
+    fn from(balance: Balance) -> MultiBalance {
+        match balance {
+            Balance::Native(native) => native.into_vec().into(),
+            Balance::Cw20(cw20_coin) => cw20_coin.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::{coin, coins, Addr, StdError};
+
+    fn cw20(address: &str, amount: u128) -> Cw20CoinVerified {
+        Cw20CoinVerified {
+            address: Addr::unchecked(address),
+            amount: Uint128::new(amount),
+        }
+    }
+
+    #[test]
+    fn add_and_subtract() {
+        let mut balance = MultiBalance::from(vec![coin(5, "uosmo"), coin(10, "uatom")]);
+        balance.add(cw20("token2", 7)).unwrap();
+        balance.add(cw20("token1", 3)).unwrap();
+        balance.add(coin(1, "uatom")).unwrap();
+        assert_eq!(
+            balance,
+            MultiBalance {
+                native: vec![coin(11, "uatom"), coin(5, "uosmo")],
+                cw20: vec![cw20("token1", 3), cw20("token2", 7)],
+            }
+        );
+        assert!(balance.has(&Asset::native("uatom", 11u128)));
+        assert!(!balance.has(&Asset::native("uatom", 12u128)));
+        assert!(!balance.has(&Asset::native("ujuno", 1u128)));
+        assert_eq!(
+            balance.to_string(),
+            "11native:uatom,5native:uosmo,3cw20:token1,7cw20:token2"
+        );
+
+        // subtracting everything removes the entry
+        balance.checked_sub(cw20("token1", 3)).unwrap();
+        balance.checked_sub(coin(5, "uosmo")).unwrap();
+        assert_eq!(
+            balance,
+            MultiBalance {
+                native: coins(11, "uatom"),
+                cw20: vec![cw20("token2", 7)],
+            }
+        );
+
+        // but not more than that
+        let err = balance.checked_sub(cw20("token2", 8)).unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+        balance.checked_sub(coin(1, "uosmo")).unwrap_err();
+        balance.add(coin(u128::MAX, "uatom")).unwrap_err();
+        assert_eq!(balance.amount_of(&Denom::Native("uatom".into())).u128(), 11);
+        assert!(!balance.is_empty());
+    }
+
+    #[test]
+    fn merge_and_pay_out() {
+        let mut balance = MultiBalance::from(coins(10, "uatom"));
+        let other = MultiBalance {
+            native: vec![coin(2, "uatom"), coin(3, "ujuno")],
+            cw20: vec![cw20("token", 4)],
+        };
+        balance.merge(other).unwrap();
+        assert_eq!(
+            balance.assets().collect::<Vec<_>>(),
+            vec![
+                Asset::native("uatom", 12u128),
+                Asset::native("ujuno", 3u128),
+                Asset::cw20(Addr::unchecked("token"), 4u128),
+            ]
+        );
+
+        // nothing is merged on overflow
+        let err = balance
+            .merge(MultiBalance::from(vec![
+                coin(1, "uosmo"),
+                coin(u128::MAX, "ujuno"),
+            ]))
+            .unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+        assert_eq!(
+            balance.amount_of(&Denom::Native("uosmo".into())),
+            Uint128::zero()
+        );
+
+        let msgs = balance.transfer_msgs("bob").unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                BankMsg::Send {
+                    to_address: "bob".into(),
+                    amount: vec![coin(12, "uatom"), coin(3, "ujuno")],
+                }
+                .into(),
+                Asset::cw20(Addr::unchecked("token"), 4u128)
+                    .transfer_msg("bob")
+                    .unwrap(),
+            ]
+        );
+        assert_eq!(
+            MultiBalance::default().transfer_msgs("bob").unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn unsorted_and_duplicated_lists() {
+        let unsorted = MultiBalance {
+            native: vec![coin(5, "uosmo"), coin(3, "uatom"), coin(2, "uosmo")],
+            cw20: vec![cw20("token2", 7), cw20("token1", 0), cw20("token2", 1)],
+        };
+        let uosmo = Denom::Native("uosmo".into());
+        assert_eq!(unsorted.amount_of(&uosmo), Uint128::new(7));
+        assert!(unsorted.has(&Asset::cw20(Addr::unchecked("token2"), 8u128)));
+        assert!(!unsorted.has(&Asset::cw20(Addr::unchecked("token2"), 9u128)));
+
+        // exactly what is reported is paid out
+        assert_eq!(
+            unsorted.transfer_msgs("bob").unwrap(),
+            vec![
+                BankMsg::Send {
+                    to_address: "bob".into(),
+                    amount: vec![coin(3, "uatom"), coin(7, "uosmo")],
+                }
+                .into(),
+                Asset::cw20(Addr::unchecked("token2"), 8u128)
+                    .transfer_msg("bob")
+                    .unwrap(),
+            ]
+        );
+
+        // changing it normalizes the lists
+        let mut balance = unsorted.clone();
+        balance.add(coin(1, "uosmo")).unwrap();
+        assert_eq!(
+            balance,
+            MultiBalance {
+                native: vec![coin(3, "uatom"), coin(8, "uosmo")],
+                cw20: vec![cw20("token2", 8)],
+            }
+        );
+        let mut balance = unsorted;
+        balance.checked_sub(coin(7, "uosmo")).unwrap();
+        assert_eq!(balance.amount_of(&uosmo), Uint128::zero());
+        assert_eq!(balance.native, coins(3, "uatom"));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            MultiBalance::from(Balance::from(vec![
+                coin(1, "b"),
+                coin(0, "c"),
+                coin(2, "a")
+            ])),
+            MultiBalance {
+                native: vec![coin(2, "a"), coin(1, "b")],
+                cw20: vec![],
+            }
+        );
+        assert_eq!(
+            MultiBalance::from(Balance::from(cw20("token", 5))),
+            MultiBalance {
+                native: vec![],
+                cw20: vec![cw20("token", 5)],
+            }
+        );
+        assert!(MultiBalance::from(cw20("token", 0)).is_empty());
+        assert!(MultiBalance::from(Asset::native("uatom", 0u128)).is_empty());
+    }
+}
@@ -4,9 +4,12 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use cw20::{
-    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, Cw20ExecuteMsg,
-    Cw20QueryMsg, Cw20ReceiveMsg, DownloadLogoResponse, MarketingInfoResponse, MinterResponse,
-    TokenInfoResponse,
+    AddressListResponse, AllAccountsResponse, AllAllowancesResponse, AllBalancesResponse,
+    AllMintersResponse, AllowanceResponse, BalanceResponse, ClawbackAdminResponse,
+    ComplianceInfoResponse, Cw20ApprovalReceiveMsg, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg,
+    DownloadLogoResponse, FeeConfigResponse, MarketingInfoResponse, MinterResponse,
+    PauseInfoResponse, PermitNonceResponse, TokenAdminResponse, TokenInfoResponse,
+    TopHoldersResponse, VestingInfoResponse,
 };
 
 fn main() {
@@ -18,12 +21,24 @@ fn main() {
     export_schema(&schema_for!(Cw20ExecuteMsg), &out_dir);
     export_schema(&schema_for!(Cw20QueryMsg), &out_dir);
     export_schema(&schema_for!(Cw20ReceiveMsg), &out_dir);
+    export_schema(&schema_for!(Cw20ApprovalReceiveMsg), &out_dir);
     export_schema(&schema_for!(AllowanceResponse), &out_dir);
     export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(TokenInfoResponse), &out_dir);
     export_schema(&schema_for!(MinterResponse), &out_dir);
     export_schema(&schema_for!(DownloadLogoResponse), &out_dir);
     export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
+    export_schema(&schema_for!(PauseInfoResponse), &out_dir);
+    export_schema(&schema_for!(ComplianceInfoResponse), &out_dir);
+    export_schema(&schema_for!(AddressListResponse), &out_dir);
+    export_schema(&schema_for!(FeeConfigResponse), &out_dir);
+    export_schema(&schema_for!(VestingInfoResponse), &out_dir);
+    export_schema(&schema_for!(AllMintersResponse), &out_dir);
+    export_schema(&schema_for!(TokenAdminResponse), &out_dir);
+    export_schema(&schema_for!(ClawbackAdminResponse), &out_dir);
+    export_schema(&schema_for!(PermitNonceResponse), &out_dir);
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+    export_schema(&schema_for!(AllBalancesResponse), &out_dir);
+    export_schema(&schema_for!(TopHoldersResponse), &out_dir);
 }
@@ -1,7 +1,10 @@
+use std::fmt;
+use std::str::FromStr;
+
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
+use cosmwasm_std::{Addr, CustomQuery, Deps, QuerierWrapper, StdError, StdResult, Uint128};
 
-use crate::{Cw20QueryMsg, TokenInfoResponse};
+use crate::{Cw20Contract, Cw20QueryMsg, TokenInfoResponse};
 
 #[cw_serde]
 pub enum Denom {
@@ -15,12 +18,6 @@ pub enum UncheckedDenom {
     Cw20(String),
 }
 
-#[cw_serde]
-pub struct DepositInfo {
-    amount: Uint128,
-    denom: UncheckedDenom,
-}
-
 impl UncheckedDenom {
     pub fn into_checked(self, deps: Deps) -> StdResult<Denom> {
         match self {
@@ -50,4 +47,68 @@ impl Denom {
             Denom::Cw20(addr) => addr.as_ref().is_empty(),
         }
     }
+
+    /// Queries how many of these tokens the given address holds
+    pub fn query_balance<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        address: T,
+    ) -> StdResult<Uint128>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        match self {
+            Denom::Native(denom) => Ok(querier.query_balance(address, denom)?.amount),
+            Denom::Cw20(addr) => Cw20Contract(addr.clone()).balance(querier, address),
+        }
+    }
+}
+
+impl From<Denom> for UncheckedDenom {
+    fn from(denom: Denom) -> UncheckedDenom {
+        match denom {
+            Denom::Native(denom) => UncheckedDenom::Native(denom),
+            Denom::Cw20(addr) => UncheckedDenom::Cw20(addr.into()),
+        }
+    }
+}
+
+/// Formats as `native:<denom>` or `cw20:<address>`
+impl fmt::Display for Denom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Denom::Native(denom) => write!(f, "native:{}", denom),
+            Denom::Cw20(addr) => write!(f, "cw20:{}", addr),
+        }
+    }
+}
+
+/// Formats as `native:<denom>` or `cw20:<address>`
+impl fmt::Display for UncheckedDenom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UncheckedDenom::Native(denom) => write!(f, "native:{}", denom),
+            UncheckedDenom::Cw20(addr) => write!(f, "cw20:{}", addr),
+        }
+    }
+}
+
+/// Parses `native:<denom>` or `cw20:<address>`. The address still needs to be checked with
+/// `into_checked`.
+impl FromStr for UncheckedDenom {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> StdResult<Self> {
+        match s.split_once(':') {
+            Some(("native", denom)) if !denom.is_empty() => {
+                Ok(UncheckedDenom::Native(denom.to_string()))
+            }
+            Some(("cw20", addr)) if !addr.is_empty() => Ok(UncheckedDenom::Cw20(addr.to_string())),
+            _ => Err(StdError::parse_err(
+                "UncheckedDenom",
+                format!("expected native:<denom> or cw20:<address>, got {}", s),
+            )),
+        }
+    }
 }
@@ -0,0 +1,418 @@
+/*!
+Typed versions of the events emitted by cw20 tokens.
+
+Besides the attributes of its `wasm` event, `cw20-base` emits one of these events for every
+transfer, send, mint and burn, for every transfer and burn forced by the clawback admin, and
+for every change of an allowance. Each converts into an `Event` with `into_event()`, and
+can be parsed back with `TryFrom<&Event>`, so indexers and tests do not have to hardcode
+attribute names. Parsing accepts both the event as built by the contract (e.g. `transfer`)
+and as reported by the chain (e.g. `wasm-transfer`), and ignores attributes it does not know,
+such as `_contract_addr` or the fee attributes.
+*/
+
+use std::str::FromStr;
+
+use cosmwasm_std::{from_slice, to_vec, Attribute, Event, StdError, StdResult, Uint128};
+use cw_utils::Expiration;
+
+/// Tokens moved from one account to another. `by` is the spender for a `TransferFrom`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransferEvent {
+    pub from: String,
+    pub to: String,
+    pub amount: Uint128,
+    pub by: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl TransferEvent {
+    pub const TYPE: &'static str = "transfer";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("from", self.from)
+            .add_attribute("to", self.to)
+            .add_attribute("amount", self.amount)
+            .add_attributes(optional("by", self.by))
+            .add_attributes(optional("memo", self.memo))
+    }
+}
+
+impl TryFrom<&Event> for TransferEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(TransferEvent {
+            from: attrs.required("from")?,
+            to: attrs.required("to")?,
+            amount: attrs.amount("amount")?,
+            by: attrs.optional("by"),
+            memo: attrs.optional("memo"),
+        })
+    }
+}
+
+/// Tokens moved to a contract, which was called with a `Cw20ReceiveMsg`. `by` is the spender
+/// for a `SendFrom`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendEvent {
+    pub from: String,
+    pub to: String,
+    pub amount: Uint128,
+    pub by: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl SendEvent {
+    pub const TYPE: &'static str = "send";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("from", self.from)
+            .add_attribute("to", self.to)
+            .add_attribute("amount", self.amount)
+            .add_attributes(optional("by", self.by))
+            .add_attributes(optional("memo", self.memo))
+    }
+}
+
+impl TryFrom<&Event> for SendEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(SendEvent {
+            from: attrs.required("from")?,
+            to: attrs.required("to")?,
+            amount: attrs.amount("amount")?,
+            by: attrs.optional("by"),
+            memo: attrs.optional("memo"),
+        })
+    }
+}
+
+/// New tokens were created for `to`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintEvent {
+    pub to: String,
+    pub amount: Uint128,
+}
+
+impl MintEvent {
+    pub const TYPE: &'static str = "mint";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("to", self.to)
+            .add_attribute("amount", self.amount)
+    }
+}
+
+impl TryFrom<&Event> for MintEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(MintEvent {
+            to: attrs.required("to")?,
+            amount: attrs.amount("amount")?,
+        })
+    }
+}
+
+/// Tokens of `from` were destroyed. `by` is the spender for a `BurnFrom`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BurnEvent {
+    pub from: String,
+    pub amount: Uint128,
+    pub by: Option<String>,
+    pub memo: Option<String>,
+}
+
+impl BurnEvent {
+    pub const TYPE: &'static str = "burn";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("from", self.from)
+            .add_attribute("amount", self.amount)
+            .add_attributes(optional("by", self.by))
+            .add_attributes(optional("memo", self.memo))
+    }
+}
+
+impl TryFrom<&Event> for BurnEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(BurnEvent {
+            from: attrs.required("from")?,
+            amount: attrs.amount("amount")?,
+            by: attrs.optional("by"),
+            memo: attrs.optional("memo"),
+        })
+    }
+}
+
+/// The clawback admin moved tokens from one account to another, bypassing allowances and
+/// vesting locks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForceTransferEvent {
+    pub admin: String,
+    pub from: String,
+    pub to: String,
+    pub amount: Uint128,
+}
+
+impl ForceTransferEvent {
+    pub const TYPE: &'static str = "force_transfer";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("admin", self.admin)
+            .add_attribute("from", self.from)
+            .add_attribute("to", self.to)
+            .add_attribute("amount", self.amount)
+    }
+}
+
+impl TryFrom<&Event> for ForceTransferEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(ForceTransferEvent {
+            admin: attrs.required("admin")?,
+            from: attrs.required("from")?,
+            to: attrs.required("to")?,
+            amount: attrs.amount("amount")?,
+        })
+    }
+}
+
+/// The clawback admin destroyed tokens of `from`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForceBurnEvent {
+    pub admin: String,
+    pub from: String,
+    pub amount: Uint128,
+}
+
+impl ForceBurnEvent {
+    pub const TYPE: &'static str = "force_burn";
+
+    pub fn into_event(self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("admin", self.admin)
+            .add_attribute("from", self.from)
+            .add_attribute("amount", self.amount)
+    }
+}
+
+impl TryFrom<&Event> for ForceBurnEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(ForceBurnEvent {
+            admin: attrs.required("admin")?,
+            from: attrs.required("from")?,
+            amount: attrs.amount("amount")?,
+        })
+    }
+}
+
+/// The allowance of `spender` on the tokens of `owner` is now `allowance`, whether it was
+/// granted, decreased or spent. A removed allowance is reported as zero. `expires` is emitted
+/// as JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllowanceChangedEvent {
+    pub owner: String,
+    pub spender: String,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+impl AllowanceChangedEvent {
+    pub const TYPE: &'static str = "allowance_changed";
+
+    pub fn into_event(self) -> Event {
+        // serializing an Expiration cannot fail
+        let expires = String::from_utf8(to_vec(&self.expires).unwrap()).unwrap();
+        Event::new(Self::TYPE)
+            .add_attribute("owner", self.owner)
+            .add_attribute("spender", self.spender)
+            .add_attribute("allowance", self.allowance)
+            .add_attribute("expires", expires)
+    }
+}
+
+impl TryFrom<&Event> for AllowanceChangedEvent {
+    type Error = StdError;
+
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let attrs = Attributes::of(event, Self::TYPE)?;
+        Ok(AllowanceChangedEvent {
+            owner: attrs.required("owner")?,
+            spender: attrs.required("spender")?,
+            allowance: attrs.amount("allowance")?,
+            expires: from_slice(attrs.required("expires")?.as_bytes())?,
+        })
+    }
+}
+
+fn optional(key: &str, value: Option<String>) -> Option<Attribute> {
+    value.map(|value| Attribute::new(key, value))
+}
+
+/// The attributes of an event, checked to be of the expected type.
+struct Attributes<'a> {
+    ty: &'static str,
+    attributes: &'a [Attribute],
+}
+
+impl<'a> Attributes<'a> {
+    fn of(event: &'a Event, ty: &'static str) -> StdResult<Self> {
+        if event.ty != ty && event.ty.strip_prefix("wasm-") != Some(ty) {
+            return Err(StdError::parse_err(
+                ty,
+                format!("unexpected event type {}", event.ty),
+            ));
+        }
+        Ok(Attributes {
+            ty,
+            attributes: &event.attributes,
+        })
+    }
+
+    fn optional(&self, key: &str) -> Option<String> {
+        self.attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+    }
+
+    fn required(&self, key: &str) -> StdResult<String> {
+        self.optional(key)
+            .ok_or_else(|| StdError::parse_err(self.ty, format!("missing attribute {}", key)))
+    }
+
+    fn amount(&self, key: &str) -> StdResult<Uint128> {
+        Uint128::from_str(&self.required(key)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transfer_round_trip() {
+        let transfer = TransferEvent {
+            from: "alice".into(),
+            to: "bob".into(),
+            amount: Uint128::new(100),
+            by: None,
+            memo: Some("invoice 7".into()),
+        };
+        let event = transfer.clone().into_event();
+        assert_eq!(
+            event,
+            Event::new("transfer")
+                .add_attribute("from", "alice")
+                .add_attribute("to", "bob")
+                .add_attribute("amount", "100")
+                .add_attribute("memo", "invoice 7")
+        );
+        assert_eq!(TransferEvent::try_from(&event).unwrap(), transfer);
+
+        // as reported by the chain, with extra attributes
+        let event = Event::new("wasm-transfer")
+            .add_attribute("_contract_addr", "token")
+            .add_attributes(event.attributes)
+            .add_attribute("fee", "1");
+        assert_eq!(TransferEvent::try_from(&event).unwrap(), transfer);
+    }
+
+    #[test]
+    fn allowance_changed_round_trip() {
+        let changed = AllowanceChangedEvent {
+            owner: "owner".into(),
+            spender: "spender".into(),
+            allowance: Uint128::new(5),
+            expires: Expiration::AtHeight(123),
+        };
+        let event = changed.clone().into_event();
+        assert_eq!(
+            event.attributes[3],
+            Attribute::new("expires", r#"{"at_height":123}"#)
+        );
+        assert_eq!(AllowanceChangedEvent::try_from(&event).unwrap(), changed);
+    }
+
+    #[test]
+    fn forced_round_trip() {
+        let transfer = ForceTransferEvent {
+            admin: "admin".into(),
+            from: "alice".into(),
+            to: "recovery".into(),
+            amount: Uint128::new(100),
+        };
+        let event = transfer.clone().into_event();
+        assert_eq!(
+            event,
+            Event::new("force_transfer")
+                .add_attribute("admin", "admin")
+                .add_attribute("from", "alice")
+                .add_attribute("to", "recovery")
+                .add_attribute("amount", "100")
+        );
+        assert_eq!(ForceTransferEvent::try_from(&event).unwrap(), transfer);
+        // not to be mistaken for a transfer through an allowance
+        TransferEvent::try_from(&event).unwrap_err();
+
+        let burn = ForceBurnEvent {
+            admin: "admin".into(),
+            from: "alice".into(),
+            amount: Uint128::new(5),
+        };
+        let event = Event::new("wasm-force_burn")
+            .add_attribute("_contract_addr", "token")
+            .add_attributes(burn.clone().into_event().attributes);
+        assert_eq!(ForceBurnEvent::try_from(&event).unwrap(), burn);
+        BurnEvent::try_from(&event).unwrap_err();
+
+        let incomplete = Event::new("force_burn")
+            .add_attribute("from", "alice")
+            .add_attribute("amount", "5");
+        let err = ForceBurnEvent::try_from(&incomplete).unwrap_err();
+        assert!(err.to_string().contains("missing attribute admin"));
+    }
+
+    #[test]
+    fn rejects_other_events() {
+        let mint = MintEvent {
+            to: "bob".into(),
+            amount: Uint128::new(100),
+        }
+        .into_event();
+        assert_eq!(
+            MintEvent::try_from(&mint).unwrap(),
+            MintEvent {
+                to: "bob".into(),
+                amount: Uint128::new(100),
+            }
+        );
+        BurnEvent::try_from(&mint).unwrap_err();
+
+        let incomplete = Event::new("burn").add_attribute("amount", "100");
+        let err = BurnEvent::try_from(&incomplete).unwrap_err();
+        assert!(err.to_string().contains("missing attribute from"));
+        let invalid = Event::new("mint")
+            .add_attribute("to", "bob")
+            .add_attribute("amount", "lots");
+        MintEvent::try_from(&invalid).unwrap_err();
+    }
+}
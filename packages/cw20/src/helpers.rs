@@ -4,9 +4,13 @@ use cosmwasm_std::{
     WasmMsg, WasmQuery,
 };
 
+use cw2::{query_supported_interfaces, ContractInterface};
+
+use crate::{amount, interfaces};
 use crate::{
-    AllowanceResponse, BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse,
-    TokenInfoResponse,
+    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
+    AllowanceResponse, BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, DownloadLogoResponse,
+    MarketingInfoResponse, MinterResponse, SpenderAllowanceInfo, TokenInfoResponse,
 };
 
 /// Cw20Contract is a wrapper around Addr that provides a lot of helpers
@@ -65,6 +69,22 @@ impl Cw20Contract {
         querier.query(&query)
     }
 
+    /// Get the token balance of the given address as a decimal string, using the decimals
+    /// of the token, eg. "12.345000"
+    pub fn format_balance<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        address: T,
+    ) -> StdResult<String>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let decimals = self.meta(querier)?.decimals;
+        let balance = self.balance(querier, address)?;
+        Ok(amount::format(balance, decimals))
+    }
+
     /// Get allowance of spender to use owner's account
     pub fn allowance<T, U, CQ>(
         &self,
@@ -93,13 +113,409 @@ impl Cw20Contract {
         querier.query(&query)
     }
 
-    /// returns true if the contract supports the allowance extension
+    /// Get the marketing metadata of the token
+    pub fn marketing_info<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<MarketingInfoResponse> {
+        let query = self.encode_smart_query(Cw20QueryMsg::MarketingInfo {})?;
+        querier.query(&query)
+    }
+
+    /// Download the logo stored on chain. Errors if there is none.
+    pub fn download_logo<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<DownloadLogoResponse> {
+        let query = self.encode_smart_query(Cw20QueryMsg::DownloadLogo {})?;
+        querier.query(&query)
+    }
+
+    /// Get one page of the allowances granted by owner. `include_expired` is only sent if set,
+    /// as tokens predating it reject the field.
+    pub fn all_allowances<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        owner: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: bool,
+    ) -> StdResult<AllAllowancesResponse>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let query = self.encode_smart_query(Cw20QueryMsg::AllAllowances {
+            owner: owner.into(),
+            start_after,
+            limit,
+            include_expired: include_expired.then_some(true),
+        })?;
+        querier.query(&query)
+    }
+
+    /// Get one page of the allowances granted to spender. `include_expired` is only sent if
+    /// set, as tokens predating it reject the field.
+    pub fn all_spender_allowances<T, CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        spender: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: bool,
+    ) -> StdResult<AllSpenderAllowancesResponse>
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let query = self.encode_smart_query(Cw20QueryMsg::AllSpenderAllowances {
+            spender: spender.into(),
+            start_after,
+            limit,
+            include_expired: include_expired.then_some(true),
+        })?;
+        querier.query(&query)
+    }
+
+    /// Get one page of the accounts holding tokens
+    pub fn all_accounts<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        let query = self.encode_smart_query(Cw20QueryMsg::AllAccounts { start_after, limit })?;
+        querier.query(&query)
+    }
+
+    /// Iterate over all allowances granted by owner, querying page after page as needed
+    pub fn all_allowances_iter<'a, T, CQ>(
+        &'a self,
+        querier: &'a QuerierWrapper<'a, CQ>,
+        owner: T,
+        include_expired: bool,
+    ) -> impl Iterator<Item = StdResult<AllowanceInfo>> + 'a
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let owner = owner.into();
+        Paginated::new(
+            move |start_after| {
+                self.all_allowances(querier, &owner, start_after, None, include_expired)
+                    .map(|res| res.allowances)
+            },
+            |info: &AllowanceInfo| info.spender.clone(),
+        )
+    }
+
+    /// Iterate over all allowances granted to spender, querying page after page as needed
+    pub fn all_spender_allowances_iter<'a, T, CQ>(
+        &'a self,
+        querier: &'a QuerierWrapper<'a, CQ>,
+        spender: T,
+        include_expired: bool,
+    ) -> impl Iterator<Item = StdResult<SpenderAllowanceInfo>> + 'a
+    where
+        T: Into<String>,
+        CQ: CustomQuery,
+    {
+        let spender = spender.into();
+        Paginated::new(
+            move |start_after| {
+                self.all_spender_allowances(querier, &spender, start_after, None, include_expired)
+                    .map(|res| res.allowances)
+            },
+            |info: &SpenderAllowanceInfo| info.owner.clone(),
+        )
+    }
+
+    /// Iterate over all accounts holding tokens, querying page after page as needed
+    pub fn all_accounts_iter<'a, CQ: CustomQuery>(
+        &'a self,
+        querier: &'a QuerierWrapper<'a, CQ>,
+    ) -> impl Iterator<Item = StdResult<String>> + 'a {
+        Paginated::new(
+            move |start_after| {
+                self.all_accounts(querier, start_after, None)
+                    .map(|res| res.accounts)
+            },
+            |account: &String| account.clone(),
+        )
+    }
+
+    /// Get the interfaces the contract advertises (see `cw2::SUPPORTED_INTERFACES`).
+    /// This is empty for contracts that do not advertise them.
+    pub fn supported_interfaces<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+    ) -> StdResult<Vec<ContractInterface>> {
+        query_supported_interfaces(querier, self.addr())
+    }
+
+    /// returns true if the contract advertises the given interface, eg. `interfaces::ALLOWANCE`
+    pub fn supports_interface<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        interface: &str,
+    ) -> StdResult<bool> {
+        let interfaces = self.supported_interfaces(querier)?;
+        Ok(interfaces.iter().any(|i| i.interface == interface))
+    }
+
+    /// Checks the advertised interfaces, or returns None if the contract does not advertise any
+    fn advertises<CQ: CustomQuery>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        interface: &str,
+    ) -> Option<bool> {
+        let interfaces = self.supported_interfaces(querier).ok()?;
+        if interfaces.is_empty() {
+            return None;
+        }
+        Some(interfaces.iter().any(|i| i.interface == interface))
+    }
+
+    /// returns true if the contract supports the allowance extension.
+    /// Contracts that do not advertise their interfaces are probed with a query.
     pub fn has_allowance<CQ: CustomQuery>(&self, querier: &QuerierWrapper<CQ>) -> bool {
-        self.allowance(querier, self.addr(), self.addr()).is_ok()
+        self.advertises(querier, interfaces::ALLOWANCE)
+            .unwrap_or_else(|| self.allowance(querier, self.addr(), self.addr()).is_ok())
     }
 
-    /// returns true if the contract supports the mintable extension
+    /// returns true if the contract supports the mintable extension.
+    /// Contracts that do not advertise their interfaces are probed with a query.
     pub fn is_mintable<CQ: CustomQuery>(&self, querier: &QuerierWrapper<CQ>) -> bool {
-        self.minter(querier).is_ok()
+        self.advertises(querier, interfaces::MINTABLE)
+            .unwrap_or_else(|| self.minter(querier).is_ok())
+    }
+}
+
+/// Walks a paginated query to the end. `fetch` returns the page after the given key, and
+/// `key` tells which key to continue after. An empty page ends the iteration, as does an
+/// error, which is returned once.
+struct Paginated<T, F, K> {
+    fetch: F,
+    key: K,
+    start_after: Option<String>,
+    page: std::vec::IntoIter<T>,
+    done: bool,
+}
+
+impl<T, F, K> Paginated<T, F, K>
+where
+    F: FnMut(Option<String>) -> StdResult<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    fn new(fetch: F, key: K) -> Self {
+        Paginated {
+            fetch,
+            key,
+            start_after: None,
+            page: vec![].into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl<T, F, K> Iterator for Paginated<T, F, K>
+where
+    F: FnMut(Option<String>) -> StdResult<Vec<T>>,
+    K: Fn(&T) -> String,
+{
+    type Item = StdResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.page.next() {
+                self.start_after = Some((self.key)(&item));
+                return Some(Ok(item));
+            }
+            if self.done {
+                return None;
+            }
+            match (self.fetch)(self.start_after.take()) {
+                Ok(page) => {
+                    self.done = page.is_empty();
+                    self.page = page.into_iter();
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{from_binary, ContractResult, Empty, SystemError, SystemResult};
+
+    const TOKEN: &str = "token";
+    const LEGACY: &str = "legacy";
+
+    // a token with five accounts, served at most two per page, that advertises the allowance
+    // extension; and a legacy token that advertises nothing. Both answer minter queries.
+    fn mock_querier() -> MockQuerier {
+        let mut querier = MockQuerier::new(&[]);
+        querier.update_wasm(|query| {
+            let msg = match query {
+                WasmQuery::Raw { contract_addr, .. } => {
+                    let data = match contract_addr.as_str() {
+                        TOKEN => to_binary(&[
+                            ContractInterface::new(interfaces::BASE, interfaces::VERSION),
+                            ContractInterface::new(interfaces::ALLOWANCE, interfaces::VERSION),
+                        ])
+                        .unwrap(),
+                        _ => Default::default(),
+                    };
+                    return SystemResult::Ok(ContractResult::Ok(data));
+                }
+                WasmQuery::Smart { contract_addr, msg }
+                    if contract_addr == TOKEN || contract_addr == LEGACY =>
+                {
+                    msg
+                }
+                _ => {
+                    return SystemResult::Err(SystemError::UnsupportedRequest {
+                        kind: "not the token".into(),
+                    })
+                }
+            };
+            match from_binary(msg).unwrap() {
+                Cw20QueryMsg::AllAccounts { start_after, limit } => {
+                    let limit = limit.unwrap_or(2).min(2) as usize;
+                    let accounts = ["a", "b", "c", "d", "e"]
+                        .iter()
+                        .filter(|a| start_after.as_deref() < Some(**a))
+                        .take(limit)
+                        .map(|a| a.to_string())
+                        .collect();
+                    let res = AllAccountsResponse { accounts };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+                }
+                Cw20QueryMsg::TokenInfo {} => {
+                    let res = TokenInfoResponse {
+                        name: "Token".into(),
+                        symbol: "TKN".into(),
+                        decimals: 6,
+                        total_supply: Uint128::new(12_345_000),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+                }
+                Cw20QueryMsg::Balance { address } => {
+                    let balance = match address.as_str() {
+                        "a" => Uint128::new(12_345_000),
+                        _ => Uint128::zero(),
+                    };
+                    let res = BalanceResponse {
+                        balance,
+                        spendable: None,
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+                }
+                Cw20QueryMsg::Minter {} => {
+                    let res: Option<MinterResponse> = None;
+                    SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+                }
+                _ => SystemResult::Ok(ContractResult::Err("unsupported query".into())),
+            }
+        });
+        querier
+    }
+
+    #[test]
+    fn paginates_accounts() {
+        let querier = mock_querier();
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+        let token = Cw20Contract(Addr::unchecked(TOKEN));
+
+        let page = token.all_accounts(&querier, None, None).unwrap();
+        assert_eq!(page.accounts, vec!["a", "b"]);
+        let page = token
+            .all_accounts(&querier, Some("b".into()), Some(10))
+            .unwrap();
+        assert_eq!(page.accounts, vec!["c", "d"]);
+
+        let accounts: StdResult<Vec<_>> = token.all_accounts_iter(&querier).collect();
+        assert_eq!(accounts.unwrap(), vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn formats_balance() {
+        let querier = mock_querier();
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+        let token = Cw20Contract(Addr::unchecked(TOKEN));
+
+        assert_eq!(token.format_balance(&querier, "a").unwrap(), "12.345000");
+        assert_eq!(token.format_balance(&querier, "f").unwrap(), "0.000000");
+    }
+
+    #[test]
+    fn include_expired_only_sent_if_set() {
+        // like a token predating include_expired, which rejects unknown fields
+        let mut querier = MockQuerier::<Empty>::new(&[]);
+        querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. }
+                if !String::from_utf8_lossy(msg).contains("include_expired") =>
+            {
+                let res = AllAllowancesResponse::default();
+                SystemResult::Ok(ContractResult::Ok(to_binary(&res).unwrap()))
+            }
+            _ => SystemResult::Ok(ContractResult::Err("unknown field".into())),
+        });
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+        let token = Cw20Contract(Addr::unchecked(TOKEN));
+
+        token
+            .all_allowances(&querier, "owner", None, None, false)
+            .unwrap();
+        token
+            .all_allowances(&querier, "owner", None, None, true)
+            .unwrap_err();
+        let spender_allowances: StdResult<Vec<_>> = token
+            .all_spender_allowances_iter(&querier, "spender", false)
+            .collect();
+        assert_eq!(spender_allowances.unwrap(), vec![]);
+    }
+
+    #[test]
+    fn iteration_stops_after_an_error() {
+        let querier = mock_querier();
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+        let token = Cw20Contract(Addr::unchecked(TOKEN));
+
+        let mut allowances = token.all_allowances_iter(&querier, "owner", false);
+        allowances.next().unwrap().unwrap_err();
+        assert!(allowances.next().is_none());
+
+        let other = Cw20Contract(Addr::unchecked("other"));
+        let mut accounts = other.all_accounts_iter(&querier);
+        accounts.next().unwrap().unwrap_err();
+        assert!(accounts.next().is_none());
+    }
+
+    #[test]
+    fn prefers_advertised_interfaces() {
+        let querier = mock_querier();
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        // answers to probing queries are ignored once interfaces are advertised
+        let token = Cw20Contract(Addr::unchecked(TOKEN));
+        assert!(token
+            .supports_interface(&querier, interfaces::ALLOWANCE)
+            .unwrap());
+        assert!(token.has_allowance(&querier));
+        assert!(!token.is_mintable(&querier));
+
+        // otherwise the contract is probed
+        let legacy = Cw20Contract(Addr::unchecked(LEGACY));
+        assert_eq!(legacy.supported_interfaces(&querier).unwrap(), vec![]);
+        assert!(!legacy.has_allowance(&querier));
+        assert!(legacy.is_mintable(&querier));
     }
 }
@@ -0,0 +1,25 @@
+/*!
+Names under which contracts advertise the parts of this spec they implement in
+`cw2::SUPPORTED_INTERFACES`, one for the base and one for every extension described in the
+README. They are advertised along with `VERSION`.
+*/
+
+/// The version of this spec
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const BASE: &str = "cw20";
+pub const BATCH: &str = "cw20/batch";
+pub const ALLOWANCE: &str = "cw20/allowance";
+pub const PERMIT: &str = "cw20/permit";
+pub const MINTABLE: &str = "cw20/mintable";
+pub const MINTERS: &str = "cw20/minters";
+pub const ADMIN: &str = "cw20/admin";
+pub const CLAWBACK: &str = "cw20/clawback";
+pub const ENUMERABLE: &str = "cw20/enumerable";
+pub const MARKETING: &str = "cw20/marketing";
+pub const PAUSABLE: &str = "cw20/pausable";
+pub const COMPLIANCE: &str = "cw20/compliance";
+pub const FEE: &str = "cw20/fee";
+pub const VESTING: &str = "cw20/vesting";
+/// Implemented by contracts that accept tokens with `Send`
+pub const RECEIVER: &str = "cw20/receiver";
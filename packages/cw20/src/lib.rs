@@ -11,27 +11,39 @@ For more information on this specification, please check out the
 
 pub use cw_utils::Expiration;
 
-pub use crate::balance::Balance;
+pub use crate::asset::{Asset, UncheckedAsset};
+pub use crate::balance::{Balance, MultiBalance};
 pub use crate::coin::{Cw20Coin, Cw20CoinVerified};
 pub use crate::denom::{Denom, UncheckedDenom};
 pub use crate::helpers::Cw20Contract;
-pub use crate::logo::{EmbeddedLogo, Logo, LogoInfo};
-pub use crate::msg::Cw20ExecuteMsg;
+pub use crate::logo::{png_dimensions, EmbeddedLogo, Logo, LogoError, LogoInfo};
+pub use crate::msg::{
+    Cw20ExecuteMsg, Cw20Send, MinterQuota, PermitPayload, RecurringAllowance, TransferFee,
+};
 pub use crate::query::{
-    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
-    AllowanceResponse, BalanceResponse, Cw20QueryMsg, DownloadLogoResponse, MarketingInfoResponse,
-    MinterResponse, SpenderAllowanceInfo, TokenInfoResponse,
+    AddressListResponse, AllAccountsResponse, AllAllowancesResponse, AllBalancesResponse,
+    AllMintersResponse, AllSpenderAllowancesResponse, AllowanceInfo, AllowanceResponse,
+    BalanceResponse, ClawbackAdminResponse, ComplianceInfoResponse, ComplianceMode, Cw20QueryMsg,
+    DownloadLogoResponse, FeeConfigResponse, MarketingInfoResponse, MinterInfo, MinterResponse,
+    PauseInfoResponse, PermitNonceResponse, SpenderAllowanceInfo, TokenAdminResponse,
+    TokenInfoResponse, TopHoldersResponse, VestingInfoResponse,
 };
-pub use crate::receiver::Cw20ReceiveMsg;
+pub use crate::receiver::{AcceptedTokens, Cw20ApprovalReceiveMsg, Cw20ReceiveMsg, ReceivedCw20};
+pub use crate::vesting::{VestingSchedule, VestingTranche};
 
+pub mod amount;
+mod asset;
 mod balance;
 mod coin;
 mod denom;
+pub mod events;
 mod helpers;
+pub mod interfaces;
 mod logo;
 mod msg;
 mod query;
 mod receiver;
+mod vesting;
 
 #[cfg(test)]
 mod tests {
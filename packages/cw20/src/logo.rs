@@ -1,5 +1,13 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Binary;
+use thiserror::Error;
+
+/// The maximum size of an embedded logo, in bytes
+pub const LOGO_SIZE_CAP: usize = 5 * 1024;
+/// The maximum width and height of an embedded PNG logo, in pixels
+pub const MAX_PNG_DIMENSION: u32 = 512;
+/// The maximum length of a logo URL
+pub const MAX_URL_LENGTH: usize = 512;
 
 /// This is used for uploading logo data, or setting it in InstantiateData
 #[cw_serde]
@@ -13,16 +21,21 @@ pub enum Logo {
 
 /// This is used to store the logo on the blockchain in an accepted format.
 /// Enforce maximum size of 5KB on all variants.
+/// New formats may be added in minor releases, so matches need a wildcard arm.
 #[cw_serde]
-
+#[non_exhaustive]
 pub enum EmbeddedLogo {
     /// Store the Logo as an SVG file. The content must conform to the spec
     /// at https://en.wikipedia.org/wiki/Scalable_Vector_Graphics
-    /// (The contract should do some light-weight sanity-check validation)
+    /// Scripts, event handlers and references to external resources are rejected.
     Svg(Binary),
     /// Store the Logo as a PNG file. This will likely only support up to 64x64 or so
     /// within the 5KB limit.
     Png(Binary),
+    /// Store the Logo as a JPEG file
+    Jpeg(Binary),
+    /// Store the Logo as a WebP file
+    Webp(Binary),
 }
 
 /// This is used to display logo info, provide a link or inform there is one
@@ -35,3 +48,481 @@ pub enum LogoInfo {
     /// There is an embedded logo on the chain, make another call to download it.
     Embedded,
 }
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LogoError {
+    #[error("Logo binary data exceeds 5KB limit")]
+    TooBig {},
+
+    #[error("Invalid logo url: {reason}")]
+    InvalidUrl { reason: String },
+
+    #[error("Invalid xml preamble for SVG")]
+    InvalidXmlPreamble {},
+
+    #[error("Unsafe SVG logo: {reason}")]
+    UnsafeSvg { reason: String },
+
+    #[error("Invalid png header")]
+    InvalidPngHeader {},
+
+    #[error("PNG logo must be at most {max}x{max} pixels, got {width}x{height}")]
+    InvalidPngDimensions { width: u32, height: u32, max: u32 },
+
+    #[error("Invalid jpeg header")]
+    InvalidJpegHeader {},
+
+    #[error("Invalid webp header")]
+    InvalidWebpHeader {},
+}
+
+impl Logo {
+    /// Checks that the logo is safe for wallets to display, and if not, returns an error
+    pub fn validate(&self) -> Result<(), LogoError> {
+        match self {
+            Logo::Url(url) => validate_url(url),
+            Logo::Embedded(logo) => logo.validate(),
+        }
+    }
+}
+
+impl EmbeddedLogo {
+    pub fn validate(&self) -> Result<(), LogoError> {
+        match self {
+            EmbeddedLogo::Svg(logo) => validate_svg(logo),
+            EmbeddedLogo::Png(logo) => validate_png(logo),
+            EmbeddedLogo::Jpeg(logo) => validate_jpeg(logo),
+            EmbeddedLogo::Webp(logo) => validate_webp(logo),
+        }
+    }
+
+    /// The mime type to serve the logo with
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            EmbeddedLogo::Svg(_) => "image/svg+xml",
+            EmbeddedLogo::Png(_) => "image/png",
+            EmbeddedLogo::Jpeg(_) => "image/jpeg",
+            EmbeddedLogo::Webp(_) => "image/webp",
+        }
+    }
+
+    pub fn data(&self) -> &Binary {
+        match self {
+            EmbeddedLogo::Svg(data)
+            | EmbeddedLogo::Png(data)
+            | EmbeddedLogo::Jpeg(data)
+            | EmbeddedLogo::Webp(data) => data,
+        }
+    }
+}
+
+fn invalid_url(reason: &str) -> LogoError {
+    LogoError::InvalidUrl {
+        reason: reason.to_string(),
+    }
+}
+
+/// Only accepts http(s) URLs with a domain name or IPv4 address as host, and without
+/// credentials, which could be used to disguise the actual host.
+fn validate_url(url: &str) -> Result<(), LogoError> {
+    if url.len() > MAX_URL_LENGTH {
+        return Err(invalid_url("too long"));
+    }
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid_url("contains whitespace"));
+    }
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| invalid_url("scheme must be http or https"))?;
+
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if authority.contains('@') {
+        return Err(invalid_url("must not contain credentials"));
+    }
+    let host = match authority.split_once(':') {
+        Some((host, port)) => {
+            if port.is_empty() || port.len() > 5 || !port.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid_url("invalid port"));
+            }
+            host
+        }
+        None => authority,
+    };
+    let labels: Vec<&str> = host.split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if host.len() > 253 || labels.len() < 2 || !labels.iter().all(valid_label) {
+        return Err(invalid_url("invalid host"));
+    }
+    Ok(())
+}
+
+/// Checks if data starts with XML preamble
+fn verify_xml_preamble(data: &[u8]) -> Result<(), LogoError> {
+    // The easiest way to perform this check would be just match on regex, however regex
+    // compilation is heavy and probably not worth it.
+
+    let preamble = data
+        .split_inclusive(|c| *c == b'>')
+        .next()
+        .ok_or(LogoError::InvalidXmlPreamble {})?;
+
+    const PREFIX: &[u8] = b"<?xml ";
+    const POSTFIX: &[u8] = b"?>";
+
+    if !(preamble.starts_with(PREFIX) && preamble.ends_with(POSTFIX)) {
+        Err(LogoError::InvalidXmlPreamble {})
+    } else {
+        Ok(())
+    }
+
+    // Additionally attributes format could be validated as they are well defined, as well as
+    // comments presence inside of preable, but it is probably not worth it.
+}
+
+fn unsafe_svg(reason: impl Into<String>) -> LogoError {
+    LogoError::UnsafeSvg {
+        reason: reason.into(),
+    }
+}
+
+/// Validates SVG logo. Without an XML parser this is a conservative text scan, which may
+/// reject some harmless logos, but no script or external resource gets through.
+fn validate_svg(logo: &[u8]) -> Result<(), LogoError> {
+    verify_xml_preamble(logo)?;
+    if logo.len() > LOGO_SIZE_CAP {
+        return Err(LogoError::TooBig {});
+    }
+
+    let svg = std::str::from_utf8(logo)
+        .map_err(|_| unsafe_svg("not valid UTF-8"))?
+        .to_ascii_lowercase();
+    for forbidden in [
+        "<script",
+        "<foreignobject",
+        "<!doctype",
+        "<!entity",
+        "javascript:",
+        "@import",
+    ] {
+        if svg.contains(forbidden) {
+            return Err(unsafe_svg(format!("contains {}", forbidden)));
+        }
+    }
+
+    // character references (`&#106;`) and CSS escapes (`\75 rl(`) could hide any of the above,
+    // so only the predefined XML entities are allowed
+    for (i, _) in svg.match_indices('&') {
+        let entity = svg[i + 1..].split(';').next().unwrap_or_default();
+        if !matches!(entity, "lt" | "gt" | "amp" | "quot" | "apos") {
+            return Err(unsafe_svg("contains a character reference"));
+        }
+    }
+    if svg.contains('\\') {
+        return Err(unsafe_svg("contains an escape sequence"));
+    }
+
+    // animations can rewrite a reference after it was checked, eg. <set attributeName="href" ..>
+    for (i, _) in svg.match_indices("attributename") {
+        let value = svg[i + 13..]
+            .trim_start()
+            .strip_prefix('=')
+            .unwrap_or_default()
+            .trim_start()
+            .trim_start_matches(['"', '\'']);
+        let name = value.split(['"', '\'']).next().unwrap_or_default();
+        if name.contains("href") {
+            return Err(unsafe_svg("animates a reference"));
+        }
+    }
+
+    // event handlers, eg. onload="...", also right after `/` or a closing quote
+    for (i, _) in svg.match_indices("on") {
+        let after_boundary =
+            svg[..i].ends_with(|c: char| c.is_ascii_whitespace() || matches!(c, '/' | '"' | '\''));
+        let name_end = svg[i + 2..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .map_or(svg.len(), |end| i + 2 + end);
+        let is_handler = name_end > i + 2 && svg[name_end..].trim_start().starts_with('=');
+        if after_boundary && is_handler {
+            return Err(unsafe_svg(format!("contains {}", &svg[i..name_end])));
+        }
+    }
+
+    // references (in `href`, `xlink:href` or `url()`) may only point into the document itself
+    let references = svg
+        .match_indices("href")
+        .filter_map(|(i, _)| svg[i + 4..].trim_start().strip_prefix('='))
+        .chain(svg.match_indices("url(").map(|(i, _)| &svg[i + 4..]));
+    for value in references {
+        let value = value.trim_start().trim_start_matches(['"', '\'']);
+        if !value.starts_with('#') {
+            return Err(unsafe_svg("references an external resource"));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the width and height of a PNG image from its IHDR chunk, which must come first.
+/// Returns None if the data does not start with a PNG header and IHDR chunk.
+pub fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    // PNG header format:
+    // 0x89 - magic byte, out of ASCII table to fail on 7-bit systems
+    // "PNG" ascii representation
+    // [0x0d, 0x0a] - dos style line ending
+    // 0x1a - dos control character, stop displaying rest of the file
+    // 0x0a - unix style line ending
+    const HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    // The IHDR chunk follows, with a length of 13: width and height as big-endian u32,
+    // followed by 5 bytes of bit depth, color type and so on
+    const IHDR: [u8; 8] = [0, 0, 0, 13, b'I', b'H', b'D', b'R'];
+
+    let rest = data.strip_prefix(&HEADER)?.strip_prefix(&IHDR)?;
+    if rest.len() < 13 {
+        return None;
+    }
+    let width = u32::from_be_bytes(rest[0..4].try_into().ok()?);
+    let height = u32::from_be_bytes(rest[4..8].try_into().ok()?);
+    Some((width, height))
+}
+
+/// Validates png logo
+fn validate_png(logo: &[u8]) -> Result<(), LogoError> {
+    if logo.len() > LOGO_SIZE_CAP {
+        return Err(LogoError::TooBig {});
+    }
+    let (width, height) = png_dimensions(logo).ok_or(LogoError::InvalidPngHeader {})?;
+    if width == 0 || height == 0 || width > MAX_PNG_DIMENSION || height > MAX_PNG_DIMENSION {
+        return Err(LogoError::InvalidPngDimensions {
+            width,
+            height,
+            max: MAX_PNG_DIMENSION,
+        });
+    }
+    Ok(())
+}
+
+/// Validates jpeg logo, which must start with a start of image marker
+fn validate_jpeg(logo: &[u8]) -> Result<(), LogoError> {
+    if logo.len() > LOGO_SIZE_CAP {
+        Err(LogoError::TooBig {})
+    } else if !logo.starts_with(&[0xff, 0xd8, 0xff]) {
+        Err(LogoError::InvalidJpegHeader {})
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates webp logo, which is a RIFF container of type WEBP
+fn validate_webp(logo: &[u8]) -> Result<(), LogoError> {
+    if logo.len() > LOGO_SIZE_CAP {
+        Err(LogoError::TooBig {})
+    } else if logo.len() < 12 || &logo[0..4] != b"RIFF" || &logo[8..12] != b"WEBP" {
+        Err(LogoError::InvalidWebpHeader {})
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        png.extend([0, 0, 0, 13]);
+        png.extend(b"IHDR");
+        png.extend(width.to_be_bytes());
+        png.extend(height.to_be_bytes());
+        png.extend([8, 6, 0, 0, 0]);
+        png
+    }
+
+    fn svg(body: &str) -> Logo {
+        let svg = format!(r#"<?xml version="1.0"?><svg>{}</svg>"#, body);
+        Logo::Embedded(EmbeddedLogo::Svg(svg.into_bytes().into()))
+    }
+
+    #[test]
+    fn validate_urls() {
+        for url in [
+            "https://example.com/logo.png",
+            "http://cdn.example.com:8080/a/b.svg?v=1#x",
+            "https://1.2.3.4/logo.png",
+            "https://my-token.io",
+        ] {
+            Logo::Url(url.into()).validate().unwrap();
+        }
+
+        for (url, reason) in [
+            ("url", "scheme must be http or https"),
+            ("javascript:alert(1)", "scheme must be http or https"),
+            ("ftp://example.com/logo.png", "scheme must be http or https"),
+            ("https://", "invalid host"),
+            ("https://localhost/logo.png", "invalid host"),
+            ("https://-bad.com/", "invalid host"),
+            ("https://exa_mple.com/", "invalid host"),
+            ("https://example.com:/logo.png", "invalid port"),
+            ("https://example.com:http/", "invalid port"),
+            (
+                "https://wallet.com@evil.com/",
+                "must not contain credentials",
+            ),
+            ("https://example.com/a logo.png", "contains whitespace"),
+        ] {
+            assert_eq!(
+                Logo::Url(url.into()).validate().unwrap_err(),
+                invalid_url(reason),
+                "{}",
+                url
+            );
+        }
+        let long = format!("https://example.com/{}", "a".repeat(MAX_URL_LENGTH));
+        Logo::Url(long).validate().unwrap_err();
+    }
+
+    #[test]
+    fn validate_svgs() {
+        svg(r##"<defs><linearGradient id="g"/></defs><rect fill="url(#g)"/><use href="#g"/>"##)
+            .validate()
+            .unwrap();
+        svg("<text>Come on = join us</text>").validate().unwrap();
+        svg(r#"<text>Tom &amp; Jerry &lt;3</text><animate attributeName="opacity" to="0"/>"#)
+            .validate()
+            .unwrap();
+
+        for (body, reason) in [
+            ("<script>alert(1)</script>", "contains <script"),
+            ("<SCRIPT>alert(1)</SCRIPT>", "contains <script"),
+            (r#"<rect onload="alert(1)"/>"#, "contains onload"),
+            (r#"<rect ONCLICK = "alert(1)"/>"#, "contains onclick"),
+            (r#"<svg/onload="alert(1)"></svg>"#, "contains onload"),
+            (r#"<rect x="1"onload="alert(1)"/>"#, "contains onload"),
+            (r#"<rect x='1'onclick="alert(1)"/>"#, "contains onclick"),
+            (r#"<a href="javascript:alert(1)"/>"#, "contains javascript:"),
+            (
+                "<foreignObject><div/></foreignObject>",
+                "contains <foreignobject",
+            ),
+            (
+                r#"<image href="https://evil.com/x.png"/>"#,
+                "references an external resource",
+            ),
+            (
+                r#"<use xlink:href = 'other.svg#a'/>"#,
+                "references an external resource",
+            ),
+            (
+                r#"<rect style="fill: url( 'https://evil.com/f')"/>"#,
+                "references an external resource",
+            ),
+            (
+                r#"<image href="data:image/svg+xml;base64,AAAA"/>"#,
+                "references an external resource",
+            ),
+            (
+                r#"<a><set attributeName="href" to="&#106;avascript:alert(1)"/></a>"#,
+                "contains a character reference",
+            ),
+            (
+                r#"<a><set attributeName="href" to="https://evil.com/"/></a>"#,
+                "animates a reference",
+            ),
+            (
+                r#"<a><animate attributeName = 'xlink:href' values="https://evil.com/"/></a>"#,
+                "animates a reference",
+            ),
+            (
+                r#"<a><animate attributeName="href" values="javascript:alert(1)"/></a>"#,
+                "contains javascript:",
+            ),
+            (
+                r#"<rect style="fill: \75 rl(https://evil.com/f)"/>"#,
+                "contains an escape sequence",
+            ),
+            (r#"<a href="&#x23;x"/>"#, "contains a character reference"),
+            (r#"<a href="&colon;"/>"#, "contains a character reference"),
+        ] {
+            assert_eq!(
+                svg(body).validate().unwrap_err(),
+                unsafe_svg(reason),
+                "{}",
+                body
+            );
+        }
+
+        let entity = br#"<?xml version="1.0"?><!DOCTYPE svg [<!ENTITY x SYSTEM "file:///etc/passwd">]><svg>&x;</svg>"#;
+        assert_eq!(
+            Logo::Embedded(EmbeddedLogo::Svg(entity.into()))
+                .validate()
+                .unwrap_err(),
+            unsafe_svg("contains <!doctype")
+        );
+        let invalid = Logo::Embedded(EmbeddedLogo::Svg(b"<svg></svg>".into()));
+        assert_eq!(
+            invalid.validate().unwrap_err(),
+            LogoError::InvalidXmlPreamble {}
+        );
+    }
+
+    #[test]
+    fn validate_pngs() {
+        assert_eq!(png_dimensions(&png(64, 32)), Some((64, 32)));
+        Logo::Embedded(EmbeddedLogo::Png(png(64, 32).into()))
+            .validate()
+            .unwrap();
+
+        for (width, height) in [(0, 10), (10, 0), (513, 10), (10, u32::MAX)] {
+            assert_eq!(
+                EmbeddedLogo::Png(png(width, height).into())
+                    .validate()
+                    .unwrap_err(),
+                LogoError::InvalidPngDimensions {
+                    width,
+                    height,
+                    max: MAX_PNG_DIMENSION
+                }
+            );
+        }
+
+        // the header alone is not enough
+        let header = png(64, 64)[..8].to_vec();
+        assert_eq!(png_dimensions(&header), None);
+        assert_eq!(
+            EmbeddedLogo::Png(header.into()).validate().unwrap_err(),
+            LogoError::InvalidPngHeader {}
+        );
+        let oversized = [png(64, 64), vec![0; LOGO_SIZE_CAP]].concat();
+        assert_eq!(
+            EmbeddedLogo::Png(oversized.into()).validate().unwrap_err(),
+            LogoError::TooBig {}
+        );
+    }
+
+    #[test]
+    fn validate_jpegs_and_webps() {
+        let jpeg = EmbeddedLogo::Jpeg(vec![0xff, 0xd8, 0xff, 0xe0, 0, 16].into());
+        jpeg.validate().unwrap();
+        assert_eq!(jpeg.mime_type(), "image/jpeg");
+        assert_eq!(
+            EmbeddedLogo::Jpeg(png(1, 1).into()).validate().unwrap_err(),
+            LogoError::InvalidJpegHeader {}
+        );
+
+        let webp = EmbeddedLogo::Webp(b"RIFF\x24\0\0\0WEBPVP8 ".into());
+        webp.validate().unwrap();
+        assert_eq!(webp.mime_type(), "image/webp");
+        assert_eq!(
+            EmbeddedLogo::Webp(b"RIFF\x24\0\0\0WAVE".into())
+                .validate()
+                .unwrap_err(),
+            LogoError::InvalidWebpHeader {}
+        );
+    }
+}
@@ -1,22 +1,42 @@
 use crate::logo::Logo;
+use crate::Cw20Coin;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Binary, Decimal, Uint128};
 use cw_utils::Expiration;
 
 #[cw_serde]
 
 pub enum Cw20ExecuteMsg {
-    /// Transfer is a base message to move tokens to another account without triggering actions
-    Transfer { recipient: String, amount: Uint128 },
+    /// Transfer is a base message to move tokens to another account without triggering actions.
+    /// An optional `memo` (e.g. to attribute a deposit) is emitted with the transfer. It is left
+    /// out of the JSON when unset, so the message still works with tokens that do not know it.
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
+    },
     /// Burn is a base message to destroy tokens forever
-    Burn { amount: Uint128 },
+    Burn {
+        amount: Uint128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
+    },
     /// Send is a base message to transfer tokens to a contract and trigger an action
-    /// on the receiving contract.
+    /// on the receiving contract. The `memo` is passed on in the `Cw20ReceiveMsg`.
     Send {
         contract: String,
         amount: Uint128,
         msg: Binary,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
     },
+    /// Only with "batch" extension. Moves tokens from the sender to every listed account in
+    /// one go. The sender is debited once, each recipient may appear only once.
+    TransferBatch { transfers: Vec<Cw20Coin> },
+    /// Only with "batch" extension. Like `Send`, but to several contracts at once, each
+    /// receiving its own amount and `msg`.
+    SendBatch { sends: Vec<Cw20Send> },
     /// Only with "approval" extension. Allows spender to access an additional amount tokens
     /// from the owner's (env.sender) account. If expires is Some(), overwrites current allowance
     /// expiration with this one.
@@ -25,6 +45,24 @@ pub enum Cw20ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Only with "approval" extension. Like `IncreaseAllowance`, and then notifies the spender
+    /// contract with a `Cw20ApprovalReceiveMsg` carrying `msg`, all in one transaction.
+    IncreaseAllowanceAndCall {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        msg: Binary,
+    },
+    /// Only with "approval" extension. Replaces the spender's allowance with `amount`. If
+    /// `period` is set, the allowance is refilled to `amount` every `period` seconds. If
+    /// `recipients` is set, the spender may only move tokens to these addresses.
+    SetAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        period: Option<u64>,
+        recipients: Option<Vec<String>>,
+    },
     /// Only with "approval" extension. Lowers the spender's access of tokens
     /// from the owner's (env.sender) account by amount. If expires is Some(), overwrites current
     /// allowance expiration with this one.
@@ -33,12 +71,31 @@ pub enum Cw20ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Only with "permit" extension. Like `IncreaseAllowance`, but for the owner of
+    /// `owner_pubkey`, who signed a `PermitPayload` off chain so anyone may submit it.
+    Permit {
+        /// The compressed (33 bytes) secp256k1 public key of the owner
+        owner_pubkey: Binary,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        /// Must be the owner's current `PermitNonce`
+        nonce: u64,
+        /// The 64 byte secp256k1 signature over the sha256 hash of the JSON encoded
+        /// `PermitPayload`
+        signature: Binary,
+    },
+    /// Only with "approval" extension. Removes up to `limit` expired allowances of the owner.
+    /// Anyone may call this.
+    PruneExpiredAllowances { owner: String, limit: Option<u32> },
     /// Only with "approval" extension. Transfers amount tokens from owner -> recipient
     /// if `env.sender` has sufficient pre-approval.
     TransferFrom {
         owner: String,
         recipient: String,
         amount: Uint128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
     },
     /// Only with "approval" extension. Sends amount tokens from owner -> contract
     /// if `env.sender` has sufficient pre-approval.
@@ -47,12 +104,27 @@ pub enum Cw20ExecuteMsg {
         contract: String,
         amount: Uint128,
         msg: Binary,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memo: Option<String>,
     },
     /// Only with "approval" extension. Destroys tokens forever
     BurnFrom { owner: String, amount: Uint128 },
+    /// Only with "approval" extension. Like `BurnFrom`, but burns from several owners at once.
+    /// `address` is the owner of each entry, and each owner may appear only once.
+    BurnFromBatch { burns: Vec<Cw20Coin> },
     /// Only with the "mintable" extension. If authorized, creates amount new tokens
     /// and adds to the recipient balance.
     Mint { recipient: String, amount: Uint128 },
+    /// Only with the "mintable" extension. If authorized, mints for every listed account in
+    /// one go. The cap applies to the total, each recipient may appear only once.
+    MintBatch { mints: Vec<Cw20Coin> },
+    /// Only with the "mintable" extension. If authorized, creates amount new tokens directly in
+    /// the contract balance and triggers an action on the receiving contract.
+    MintAndSend {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
     /// Only with the "mintable" extension. The current minter may set
     /// a new minter. Setting the minter to None will remove the
     /// token's minter forever.
@@ -70,4 +142,112 @@ pub enum Cw20ExecuteMsg {
     },
     /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
     UploadLogo(Logo),
+    /// Only with the "pausable" extension. If authorized, freezes all token movements
+    /// (transfers, sends, burns and mints) until `Unpause` is called.
+    Pause {},
+    /// Only with the "pausable" extension. If authorized, lifts a previous `Pause`.
+    Unpause {},
+    /// Only with the "compliance" extension. If authorized, adds and removes addresses from
+    /// the blocklist. In blocklist mode, listed addresses can neither send nor receive tokens.
+    UpdateBlocklist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Only with the "compliance" extension. If authorized, adds and removes addresses from
+    /// the allowlist. In allowlist mode, only listed addresses can send or receive tokens.
+    UpdateAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Only with the "fee" extension. If authorized, replaces the transfer fee.
+    /// Setting None removes the fee.
+    UpdateFee { fee: Option<TransferFee> },
+    /// Only with the "minters" extension. If authorized, registers an additional minter
+    /// with its own cap on the total it may mint and an optional quota per period.
+    AddMinter {
+        minter: String,
+        cap: Option<Uint128>,
+        quota: Option<MinterQuota>,
+    },
+    /// Only with the "minters" extension. If authorized, revokes a registered minter.
+    RemoveMinter { minter: String },
+    /// Only with the "minters" extension. If authorized, replaces the cap and quota of a
+    /// registered minter. Setting None removes the limit.
+    UpdateMinterQuota {
+        minter: String,
+        cap: Option<Uint128>,
+        quota: Option<MinterQuota>,
+    },
+    /// Only with the "admin" extension. If authorized, renames the token. Fields left
+    /// empty are not changed.
+    UpdateTokenInfo {
+        name: Option<String>,
+        symbol: Option<String>,
+    },
+    /// Only with the "admin" extension. If authorized, replaces the cap of the minter.
+    /// None removes the cap.
+    UpdateCap { cap: Option<Uint128> },
+    /// Only with the "clawback" extension. If authorized, moves amount tokens from any
+    /// account to the recipient, without an allowance.
+    ForceTransfer {
+        from: String,
+        to: String,
+        amount: Uint128,
+    },
+    /// Only with the "clawback" extension. If authorized, destroys amount tokens of any
+    /// account, without an allowance.
+    ForceBurn { from: String, amount: Uint128 },
+    /// Only with the "clawback" extension. If authorized, gives up the clawback role for good.
+    RenounceClawbackAdmin {},
+}
+
+/// A fee charged on every transfer and send, credited to the `recipient` (treasury).
+/// Transfers from or to any of the `exempt` addresses are free.
+#[cw_serde]
+pub struct TransferFee {
+    /// The share of the transferred amount taken as fee, must be lower than 1
+    pub rate: Decimal,
+    pub recipient: String,
+    pub exempt: Vec<String>,
+}
+
+/// Limits how much a registered minter may mint over a rolling window. Every mint uses up part
+/// of `amount`, which is released again linearly over `period` seconds. So the minter can mint
+/// at most `amount` at once, and then only as fast as the quota is released, with no point in
+/// time at which the whole quota resets.
+#[cw_serde]
+pub struct MinterQuota {
+    pub amount: Uint128,
+    /// The length of a period in seconds
+    pub period: u64,
+}
+
+/// What the owner signs to grant a `Permit`. It is bound to the chain and token contract,
+/// so a signature cannot be replayed anywhere else. The JSON encoding signed is the one of
+/// this struct, with the fields in this order and no whitespace.
+#[cw_serde]
+pub struct PermitPayload {
+    pub chain_id: String,
+    /// The address of the token contract
+    pub contract: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+}
+
+/// An allowance refilled to `amount` at the start of every period
+#[cw_serde]
+pub struct RecurringAllowance {
+    pub amount: Uint128,
+    /// The length of a period in seconds
+    pub period: u64,
+}
+
+/// A single entry of the `SendBatch` message
+#[cw_serde]
+pub struct Cw20Send {
+    pub contract: String,
+    pub amount: Uint128,
+    pub msg: Binary,
 }
@@ -2,9 +2,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Timestamp, Uint128};
 
+use crate::coin::Cw20Coin;
 use crate::logo::LogoInfo;
+use crate::msg::{MinterQuota, RecurringAllowance, TransferFee};
+use crate::vesting::VestingSchedule;
 use cw_utils::Expiration;
 
 #[cw_serde]
@@ -34,13 +37,76 @@ pub enum Cw20QueryMsg {
     /// this contract.
     /// Return type: DownloadLogoResponse.
     DownloadLogo {},
+    /// Only with "pausable" extension.
+    /// Returns who can pause the token and whether it is currently paused.
+    /// Return type: PauseInfoResponse.
+    PauseInfo {},
+    /// Only with "compliance" extension.
+    /// Returns the compliance admin and mode, or None if compliance checks are disabled.
+    /// Return type: Option<ComplianceInfoResponse>.
+    ComplianceInfo {},
+    /// Only with "compliance" extension.
+    /// Returns the blocked addresses. Supports pagination.
+    /// Return type: AddressListResponse.
+    Blocklist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "compliance" extension.
+    /// Returns the allowed addresses. Supports pagination.
+    /// Return type: AddressListResponse.
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "fee" extension.
+    /// Returns the transfer fee and who can update it.
+    /// Return type: FeeConfigResponse.
+    FeeConfig {},
+    /// Only with "minters" extension.
+    /// Returns the registered minters with their limits and usage. Supports pagination.
+    /// Return type: AllMintersResponse.
+    AllMinters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "vesting" extension.
+    /// Returns the vesting schedule of the given address and how much is still locked.
+    /// Return type: VestingInfoResponse.
+    VestingInfo { address: String },
+    /// Only with "admin" extension.
+    /// Returns who can update the token name, symbol and cap.
+    /// Return type: TokenAdminResponse.
+    TokenAdmin {},
+    /// Only with "clawback" extension.
+    /// Returns who can force transfers and burns, if anyone.
+    /// Return type: ClawbackAdminResponse.
+    ClawbackAdmin {},
+    /// Only with "permit" extension.
+    /// Returns the nonce the next permit signed by the owner must use.
+    /// Return type: PermitNonceResponse.
+    PermitNonce { owner: String },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this owner has approved. Supports pagination.
+    /// Expired allowances are skipped unless `include_expired` is set.
     /// Return type: AllAllowancesResponse.
     AllAllowances {
         owner: String,
         start_after: Option<String>,
         limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        include_expired: Option<bool>,
+    },
+    /// Only with "enumerable" extension (and "allowances")
+    /// Returns all allowances this spender has been granted. Supports pagination.
+    /// Expired allowances are skipped unless `include_expired` is set.
+    /// Return type: AllSpenderAllowancesResponse.
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        include_expired: Option<bool>,
     },
     /// Only with "enumerable" extension
     /// Returns all accounts that have balances. Supports pagination.
@@ -49,11 +115,32 @@ pub enum Cw20QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Only with "enumerable" extension
+    /// Returns all accounts with their balances, ordered by address. Supports pagination.
+    /// Return type: AllBalancesResponse.
+    AllBalances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Only with "enumerable" extension
+    /// Returns the accounts with the highest balances, largest first, and accounts with equal
+    /// balances by address, descending. Pass the last holder of the previous page as
+    /// `start_after` to continue.
+    /// Return type: TopHoldersResponse.
+    TopHolders {
+        limit: Option<u32>,
+        start_after: Option<Cw20Coin>,
+    },
 }
 
 #[cw_serde]
 pub struct BalanceResponse {
     pub balance: Uint128,
+    /// Only with "vesting" extension. The part of the balance that has vested and can be
+    /// moved right now. Not set for accounts without a vesting schedule, nor by tokens that do
+    /// not lock balances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -66,8 +153,15 @@ pub struct TokenInfoResponse {
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 pub struct AllowanceResponse {
+    /// What is left to spend, in the current period for recurring allowances
     pub allowance: Uint128,
     pub expires: Expiration,
+    /// If set, the allowance is refilled to `recurring.amount` every `recurring.period` seconds
+    pub recurring: Option<RecurringAllowance>,
+    /// When a recurring allowance is refilled next
+    pub next_reset: Option<Timestamp>,
+    /// If set, the spender may only move tokens to these addresses, and cannot burn them
+    pub recipients: Option<Vec<String>>,
 }
 
 #[cw_serde]
@@ -91,6 +185,91 @@ pub struct MarketingInfoResponse {
     pub marketing: Option<Addr>,
 }
 
+#[cw_serde]
+pub struct PauseInfoResponse {
+    /// The address (if any) who can pause and unpause the token
+    pub pauser: Option<String>,
+    /// While paused, no tokens can be moved, minted or burned
+    pub paused: bool,
+}
+
+/// Decides which addresses may send and receive tokens
+#[cw_serde]
+pub enum ComplianceMode {
+    /// Everyone except the addresses on the blocklist
+    Blocklist,
+    /// Only the addresses on the allowlist
+    Allowlist,
+}
+
+#[cw_serde]
+pub struct ComplianceInfoResponse {
+    /// The address who can update the blocklist and allowlist
+    pub admin: String,
+    pub mode: ComplianceMode,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AddressListResponse {
+    pub addresses: Vec<String>,
+}
+
+#[cw_serde]
+pub struct FeeConfigResponse {
+    /// The address (if any) who can update the fee
+    pub admin: Option<String>,
+    /// The fee charged on transfers, None if they are free
+    pub fee: Option<TransferFee>,
+}
+
+#[cw_serde]
+pub struct TokenAdminResponse {
+    /// The address (if any) who can update the token name, symbol and cap
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct ClawbackAdminResponse {
+    /// The address (if any) who can force transfers and burns
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct MinterInfo {
+    pub minter: String,
+    /// The maximum this minter may mint in total, None if unlimited
+    pub cap: Option<Uint128>,
+    /// How much this minter has minted so far
+    pub minted: Uint128,
+    pub quota: Option<MinterQuota>,
+    /// How much of the quota is in use. Minted tokens are released from it linearly over
+    /// the quota period.
+    pub quota_used: Uint128,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AllMintersResponse {
+    pub minters: Vec<MinterInfo>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct VestingInfoResponse {
+    /// The amount vesting under the schedule, locked or not
+    pub amount: Uint128,
+    /// The part of `amount` that has not vested yet
+    pub locked: Uint128,
+    /// None if the address has no vesting schedule
+    pub schedule: Option<VestingSchedule>,
+}
+
 /// When we download an embedded logo, we get this response type.
 /// We expect a SPA to be able to accept this info and display it.
 #[cw_serde]
@@ -104,6 +283,12 @@ pub struct AllowanceInfo {
     pub spender: String,
     pub allowance: Uint128,
     pub expires: Expiration,
+    /// If set, the allowance is refilled to `recurring.amount` every `recurring.period` seconds
+    pub recurring: Option<RecurringAllowance>,
+    /// When a recurring allowance is refilled next
+    pub next_reset: Option<Timestamp>,
+    /// If set, the spender may only move tokens to these addresses, and cannot burn them
+    pub recipients: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -116,6 +301,12 @@ pub struct SpenderAllowanceInfo {
     pub owner: String,
     pub allowance: Uint128,
     pub expires: Expiration,
+    /// If set, the allowance is refilled to `recurring.amount` every `recurring.period` seconds
+    pub recurring: Option<RecurringAllowance>,
+    /// When a recurring allowance is refilled next
+    pub next_reset: Option<Timestamp>,
+    /// If set, the spender may only move tokens to these addresses, and cannot burn them
+    pub recipients: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -127,3 +318,15 @@ pub struct AllSpenderAllowancesResponse {
 pub struct AllAccountsResponse {
     pub accounts: Vec<String>,
 }
+
+#[cw_serde]
+#[derive(Default)]
+pub struct AllBalancesResponse {
+    pub balances: Vec<Cw20Coin>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct TopHoldersResponse {
+    pub holders: Vec<Cw20Coin>,
+}
@@ -1,5 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_binary, Binary, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    from_slice, to_binary, Addr, Api, Binary, CosmosMsg, MessageInfo, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{Asset, Denom};
 
 /// Cw20ReceiveMsg should be de/serialized under `Receive()` variant in a ExecuteMsg
 #[cw_serde]
@@ -8,6 +14,10 @@ pub struct Cw20ReceiveMsg {
     pub sender: String,
     pub amount: Uint128,
     pub msg: Binary,
+    /// The memo of the `Send` or `SendFrom`, if any. It is left out of the JSON when unset,
+    /// so receivers built against older versions keep accepting the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 impl Cw20ReceiveMsg {
@@ -27,11 +37,217 @@ impl Cw20ReceiveMsg {
         };
         Ok(execute.into())
     }
+
+    /// Checks the message received in `ExecuteMsg::Receive`. The token is `info.sender`, the
+    /// contract that called us, which is checked against `accepted`. `sender` is validated and
+    /// `msg` is deserialized.
+    pub fn into_checked<T: DeserializeOwned>(
+        self,
+        api: &dyn Api,
+        info: &MessageInfo,
+        accepted: AcceptedTokens,
+    ) -> StdResult<ReceivedCw20<T>> {
+        let token = &info.sender;
+        let is_accepted = match accepted {
+            AcceptedTokens::Any => true,
+            AcceptedTokens::Denom(Denom::Cw20(expected)) => expected == token,
+            AcceptedTokens::Denom(Denom::Native(_)) => false,
+            AcceptedTokens::Allowlist(allowed) => allowed.contains(token),
+        };
+        if !is_accepted {
+            return Err(StdError::generic_err(format!(
+                "Tokens of {} are not accepted",
+                token
+            )));
+        }
+        Ok(ReceivedCw20 {
+            token: token.clone(),
+            sender: api.addr_validate(&self.sender)?,
+            amount: self.amount,
+            msg: from_slice(&self.msg)?,
+            memo: self.memo,
+        })
+    }
+}
+
+/// The tokens a contract accepts, checked by `Cw20ReceiveMsg::into_checked`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AcceptedTokens<'a> {
+    /// Tokens from any contract. Only use this if the contract checks the token itself.
+    Any,
+    /// Only the given token. A native denom accepts no cw20 tokens at all.
+    Denom(&'a Denom),
+    /// Only the listed token contracts
+    Allowlist(&'a [Addr]),
+}
+
+/// A `Cw20ReceiveMsg` checked by the receiving contract
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceivedCw20<T> {
+    /// The token contract that sent the tokens
+    pub token: Addr,
+    /// The account that sent the tokens, as reported by the token contract. It is only as
+    /// trustworthy as that contract, so only use it for actions in the account's favor (like
+    /// crediting a deposit, not withdrawals).
+    pub sender: Addr,
+    pub amount: Uint128,
+    pub msg: T,
+    pub memo: Option<String>,
+}
+
+impl<T> ReceivedCw20<T> {
+    /// The tokens received
+    pub fn asset(&self) -> Asset {
+        Asset::cw20(self.token.clone(), self.amount)
+    }
 }
 
-// This is just a helper to properly serialize the above message
+/// Cw20ApprovalReceiveMsg should be de/serialized under `ReceiveApproval()` variant in a
+/// ExecuteMsg. It tells the spender that `owner` raised its allowance by `amount`.
+#[cw_serde]
+pub struct Cw20ApprovalReceiveMsg {
+    pub owner: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+impl Cw20ApprovalReceiveMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = ReceiverExecuteMsg::ReceiveApproval(self);
+        to_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(self, contract_addr: T) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above messages
 #[cw_serde]
 
 enum ReceiverExecuteMsg {
     Receive(Cw20ReceiveMsg),
+    ReceiveApproval(Cw20ApprovalReceiveMsg),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::{mock_info, MockApi};
+
+    #[cw_serde]
+    enum HookMsg {
+        Deposit { id: u64 },
+    }
+
+    fn receive(sender: &str, msg: &HookMsg) -> Cw20ReceiveMsg {
+        Cw20ReceiveMsg {
+            sender: sender.into(),
+            amount: Uint128::new(100),
+            msg: to_binary(msg).unwrap(),
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn into_checked_works() {
+        let api = MockApi::default();
+        let info = mock_info("token", &[]);
+        let msg = HookMsg::Deposit { id: 7 };
+
+        let received: ReceivedCw20<HookMsg> = receive("alice", &msg)
+            .into_checked(&api, &info, AcceptedTokens::Any)
+            .unwrap();
+        assert_eq!(
+            received,
+            ReceivedCw20 {
+                token: Addr::unchecked("token"),
+                sender: Addr::unchecked("alice"),
+                amount: Uint128::new(100),
+                msg: msg.clone(),
+                memo: None,
+            }
+        );
+        assert_eq!(
+            received.asset(),
+            Asset::cw20(Addr::unchecked("token"), 100u128)
+        );
+
+        // the sender must be a valid address, and the message of the expected type
+        receive("", &msg)
+            .into_checked::<HookMsg>(&api, &info, AcceptedTokens::Any)
+            .unwrap_err();
+        let mut wrapper = receive("alice", &msg);
+        wrapper.msg = to_binary(&"deposit").unwrap();
+        wrapper
+            .into_checked::<HookMsg>(&api, &info, AcceptedTokens::Any)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn into_checked_restricts_tokens() {
+        let api = MockApi::default();
+        let msg = HookMsg::Deposit { id: 7 };
+        let expected = Denom::Cw20(Addr::unchecked("token"));
+        let allowed = [Addr::unchecked("token"), Addr::unchecked("other")];
+
+        for accepted in [
+            AcceptedTokens::Denom(&expected),
+            AcceptedTokens::Allowlist(&allowed),
+        ] {
+            let info = mock_info("token", &[]);
+            receive("alice", &msg)
+                .into_checked::<HookMsg>(&api, &info, accepted)
+                .unwrap();
+
+            let info = mock_info("fake", &[]);
+            let err = receive("alice", &msg)
+                .into_checked::<HookMsg>(&api, &info, accepted)
+                .unwrap_err();
+            assert_eq!(
+                err,
+                StdError::generic_err("Tokens of fake are not accepted")
+            );
+        }
+
+        let native = Denom::Native("ustake".into());
+        let info = mock_info("token", &[]);
+        receive("alice", &msg)
+            .into_checked::<HookMsg>(&api, &info, AcceptedTokens::Denom(&native))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn memo_is_left_out_when_unset() {
+        let msg = Cw20ReceiveMsg {
+            sender: "sender".into(),
+            amount: Uint128::new(100),
+            msg: Binary::default(),
+            memo: None,
+        };
+        assert_eq!(
+            msg.clone().into_binary().unwrap(),
+            Binary::from(br#"{"receive":{"sender":"sender","amount":"100","msg":""}}"#)
+        );
+
+        let msg = Cw20ReceiveMsg {
+            memo: Some("deposit 42".into()),
+            ..msg
+        };
+        assert_eq!(
+            msg.into_binary().unwrap(),
+            Binary::from(
+                br#"{"receive":{"sender":"sender","amount":"100","msg":"","memo":"deposit 42"}}"#
+            )
+        );
+    }
 }
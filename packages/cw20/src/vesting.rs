@@ -0,0 +1,149 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{StdError, StdResult, Timestamp, Uint128};
+
+/// Describes how a locked balance becomes spendable over time
+#[cw_serde]
+pub enum VestingSchedule {
+    /// The whole amount unlocks at once at `time`
+    Cliff { time: Timestamp },
+    /// The amount unlocks linearly, starting at `start` and fully vested at `end`
+    Linear { start: Timestamp, end: Timestamp },
+    /// Each tranche unlocks its `amount` at its `time`. The tranches must add up
+    /// to the whole vesting amount.
+    Tranches { tranches: Vec<VestingTranche> },
+}
+
+#[cw_serde]
+pub struct VestingTranche {
+    pub time: Timestamp,
+    pub amount: Uint128,
+}
+
+impl VestingSchedule {
+    /// Checks that the schedule is well formed for vesting `amount` tokens
+    pub fn validate(&self, amount: Uint128) -> StdResult<()> {
+        match self {
+            VestingSchedule::Cliff { .. } => Ok(()),
+            VestingSchedule::Linear { start, end } => {
+                if end <= start {
+                    return Err(StdError::generic_err(
+                        "Linear vesting must end after it starts",
+                    ));
+                }
+                Ok(())
+            }
+            VestingSchedule::Tranches { tranches } => {
+                let total = tranches
+                    .iter()
+                    .try_fold(Uint128::zero(), |acc, t| acc.checked_add(t.amount))?;
+                if total != amount {
+                    return Err(StdError::generic_err(
+                        "Vesting tranches must add up to the vesting amount",
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns how much of `amount` is still locked at `now`
+    pub fn locked(&self, amount: Uint128, now: Timestamp) -> Uint128 {
+        match self {
+            VestingSchedule::Cliff { time } => {
+                if now < *time {
+                    amount
+                } else {
+                    Uint128::zero()
+                }
+            }
+            VestingSchedule::Linear { start, end } => {
+                if now <= *start {
+                    amount
+                } else if now >= *end {
+                    Uint128::zero()
+                } else {
+                    let remaining = end.nanos() - now.nanos();
+                    let duration = end.nanos() - start.nanos();
+                    amount.multiply_ratio(remaining, duration)
+                }
+            }
+            VestingSchedule::Tranches { tranches } => tranches
+                .iter()
+                .filter(|t| now < t.time)
+                .map(|t| t.amount)
+                .sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cliff_unlocks_at_once() {
+        let schedule = VestingSchedule::Cliff {
+            time: Timestamp::from_seconds(100),
+        };
+        let amount = Uint128::new(1000);
+        assert_eq!(schedule.locked(amount, Timestamp::from_seconds(99)), amount);
+        assert_eq!(
+            schedule.locked(amount, Timestamp::from_seconds(100)),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn linear_unlocks_gradually() {
+        let schedule = VestingSchedule::Linear {
+            start: Timestamp::from_seconds(100),
+            end: Timestamp::from_seconds(200),
+        };
+        let amount = Uint128::new(1000);
+        schedule.validate(amount).unwrap();
+        assert_eq!(schedule.locked(amount, Timestamp::from_seconds(50)), amount);
+        assert_eq!(
+            schedule.locked(amount, Timestamp::from_seconds(125)),
+            Uint128::new(750)
+        );
+        assert_eq!(
+            schedule.locked(amount, Timestamp::from_seconds(300)),
+            Uint128::zero()
+        );
+
+        let schedule = VestingSchedule::Linear {
+            start: Timestamp::from_seconds(100),
+            end: Timestamp::from_seconds(100),
+        };
+        schedule.validate(amount).unwrap_err();
+    }
+
+    #[test]
+    fn tranches_unlock_in_steps() {
+        let schedule = VestingSchedule::Tranches {
+            tranches: vec![
+                VestingTranche {
+                    time: Timestamp::from_seconds(100),
+                    amount: Uint128::new(300),
+                },
+                VestingTranche {
+                    time: Timestamp::from_seconds(200),
+                    amount: Uint128::new(700),
+                },
+            ],
+        };
+        schedule.validate(Uint128::new(1000)).unwrap();
+        schedule.validate(Uint128::new(999)).unwrap_err();
+
+        let amount = Uint128::new(1000);
+        assert_eq!(schedule.locked(amount, Timestamp::from_seconds(99)), amount);
+        assert_eq!(
+            schedule.locked(amount, Timestamp::from_seconds(150)),
+            Uint128::new(700)
+        );
+        assert_eq!(
+            schedule.locked(amount, Timestamp::from_seconds(200)),
+            Uint128::zero()
+        );
+    }
+}
@@ -2,10 +2,8 @@ use cosmwasm_schema::cw_serde;
 use cw_utils::{must_pay, PaymentError};
 use thiserror::Error;
 
-use cosmwasm_std::{
-    to_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, MessageInfo, StdResult, Uint128, WasmMsg,
-};
-use cw20::{Denom, UncheckedDenom};
+use cosmwasm_std::{Addr, CosmosMsg, Deps, MessageInfo, StdResult, Uint128};
+use cw20::{Asset, Denom, UncheckedDenom};
 
 /// Information about the deposit required to create a proposal.
 #[cw_serde]
@@ -88,54 +86,19 @@ impl DepositInfo {
         depositor: &Addr,
         contract: &Addr,
     ) -> StdResult<Vec<CosmosMsg>> {
-        let take_deposit_msg: Vec<CosmosMsg> = if let DepositInfo {
-            amount,
-            denom: Denom::Cw20(address),
-            ..
-        } = self
-        {
-            // into_checked() makes sure this isn't the case, but just for
-            // posterity.
-            if amount.is_zero() {
-                vec![]
-            } else {
-                vec![WasmMsg::Execute {
-                    contract_addr: address.to_string(),
-                    funds: vec![],
-                    msg: to_binary(&cw20::Cw20ExecuteMsg::TransferFrom {
-                        owner: depositor.to_string(),
-                        recipient: contract.to_string(),
-                        amount: *amount,
-                    })?,
-                }
-                .into()]
-            }
-        } else {
-            vec![]
-        };
-        Ok(take_deposit_msg)
+        // native deposits are paid along with the proposal, and into_checked() makes sure the
+        // amount isn't zero, but just for posterity.
+        if matches!(self.denom, Denom::Native(_)) || self.amount.is_zero() {
+            return Ok(vec![]);
+        }
+        Ok(vec![self.asset().transfer_from_msg(depositor, contract)?])
     }
 
     pub fn get_return_deposit_message(&self, depositor: &Addr) -> StdResult<CosmosMsg> {
-        let message = match &self.denom {
-            Denom::Native(denom) => BankMsg::Send {
-                to_address: depositor.to_string(),
-                amount: vec![Coin {
-                    amount: self.amount,
-                    denom: denom.to_string(),
-                }],
-            }
-            .into(),
-            Denom::Cw20(address) => WasmMsg::Execute {
-                contract_addr: address.to_string(),
-                msg: to_binary(&cw20::Cw20ExecuteMsg::Transfer {
-                    recipient: depositor.to_string(),
-                    amount: self.amount,
-                })?,
-                funds: vec![],
-            }
-            .into(),
-        };
-        Ok(message)
+        self.asset().transfer_msg(depositor)
+    }
+
+    fn asset(&self) -> Asset {
+        Asset::new(self.denom.clone(), self.amount)
     }
 }
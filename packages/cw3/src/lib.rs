@@ -24,3 +24,8 @@ pub use crate::query::{
     Cw3QueryMsg, ProposalListResponse, ProposalResponse, Status, VoteInfo, VoteListResponse,
     VoteResponse, VoterDetail, VoterListResponse, VoterResponse,
 };
+
+/// The name under which contracts advertise this spec in `cw2::SUPPORTED_INTERFACES`
+pub const INTERFACE: &str = "cw3";
+/// The version of this spec, advertised along with `INTERFACE`
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -32,3 +32,8 @@ pub use crate::query::{
     MemberResponse, TotalWeightResponse, MEMBERS_CHANGELOG, MEMBERS_CHECKPOINTS, MEMBERS_KEY,
     TOTAL_KEY, TOTAL_KEY_CHANGELOG, TOTAL_KEY_CHECKPOINTS,
 };
+
+/// The name under which contracts advertise this spec in `cw2::SUPPORTED_INTERFACES`
+pub const INTERFACE: &str = "cw4";
+/// The version of this spec, advertised along with `INTERFACE`
+pub const INTERFACE_VERSION: &str = env!("CARGO_PKG_VERSION");